@@ -0,0 +1,46 @@
+// Compares formatting 100 small files one at a time against formatting them
+// all with one reused printer.
+//
+//     cargo bench --bench unparse_all
+
+use prettyplease::Config;
+use std::time::Instant;
+
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    let files: Vec<syn::File> = (0..100)
+        .map(|i| {
+            let src = format!(
+                "pub struct S{i} {{ a: u32, b: Vec<String> }}
+                impl S{i} {{
+                    pub fn new(a: u32) -> Self {{ S{i} {{ a, b: Vec::new() }} }}
+                    fn sum(&self) -> usize {{ self.b.iter().map(|s| s.len()).sum::<usize>() + self.a as usize }}
+                }}",
+            );
+            syn::parse_file(&src).unwrap()
+        })
+        .collect();
+    let config = Config::new();
+
+    let start = Instant::now();
+    let mut individually = Vec::new();
+    for _ in 0..ITERATIONS {
+        individually = files
+            .iter()
+            .map(|file| prettyplease::unparse_with(file, &config))
+            .collect();
+    }
+    let elapsed_individually = start.elapsed() / ITERATIONS;
+
+    let start = Instant::now();
+    let mut all = Vec::new();
+    for _ in 0..ITERATIONS {
+        all = prettyplease::unparse_all(&files, &config);
+    }
+    let elapsed_all = start.elapsed() / ITERATIONS;
+
+    assert_eq!(individually, all);
+    println!("unparse_with x100: {:?}", elapsed_individually);
+    println!("unparse_all  x100: {:?}", elapsed_all);
+}
@@ -1,12 +1,14 @@
 // Adapted from https://github.com/rust-lang/rust/blob/1.57.0/compiler/rustc_ast_pretty/src/pp.rs.
 // See "Algorithm notes" in the crate-level rustdoc.
 
+use crate::color;
 use crate::ring::RingBuffer;
-use crate::{MARGIN, MIN_SPACE};
+use crate::{Config, INDENT, MIN_SPACE};
 use std::borrow::Cow;
 use std::cmp;
 use std::collections::VecDeque;
 use std::iter;
+use std::mem;
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum Breaks {
@@ -23,6 +25,10 @@ pub struct BreakToken {
     pub no_break: Option<char>,
     pub if_nonempty: bool,
     pub never_break: bool,
+    // A hanging break is measured only up to the next break at any depth,
+    // that is the first line of what follows instead of all of it, and once
+    // taken it indents the rest of its box by its offset.
+    pub hanging: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -48,6 +54,7 @@ enum PrintFrame {
 pub const SIZE_INFINITY: isize = 0xffff;
 
 pub struct Printer {
+    pub config: Config,
     out: String,
     // Number of spaces left on line
     space: isize,
@@ -69,6 +76,10 @@ pub struct Printer {
     indent: usize,
     // Buffered indentation to avoid writing trailing whitespace
     pending_indentation: usize,
+    // Tabs to write before the buffered indentation, if indenting with tabs
+    pending_tabs: usize,
+    // Ring-buffer index of a hanging break whose size is not yet known
+    pending_hanging: Option<usize>,
 }
 
 #[derive(Clone)]
@@ -78,26 +89,49 @@ struct BufEntry {
 }
 
 impl Printer {
-    pub fn new() -> Self {
-        Printer {
+    pub fn new(config: &Config) -> Self {
+        let mut printer = Printer {
+            config: config.clone(),
             out: String::new(),
-            space: MARGIN,
+            space: config.max_width as isize,
             buf: RingBuffer::new(),
             left_total: 0,
             right_total: 0,
             scan_stack: VecDeque::new(),
             print_stack: Vec::new(),
-            indent: 0,
+            indent: config.base_indent,
             pending_indentation: 0,
-        }
+            pending_tabs: 0,
+            pending_hanging: None,
+        };
+        printer.start_line(config.base_indent);
+        printer
     }
 
     pub fn eof(mut self) -> String {
+        self.take_output()
+    }
+
+    // Flushes and returns everything printed so far, leaving the printer ready
+    // to print another file while keeping its buffers' allocations.
+    pub fn take_output(&mut self) -> String {
         if !self.scan_stack.is_empty() {
             self.check_stack(0);
             self.advance_left();
         }
-        self.out
+        if self.config.snippet {
+            let len = self.out.trim_end_matches('\n').len();
+            self.out.truncate(len);
+        }
+        self.buf.clear();
+        self.left_total = 0;
+        self.right_total = 0;
+        self.scan_stack.clear();
+        self.pending_hanging = None;
+        self.print_stack.clear();
+        self.indent = self.config.base_indent;
+        self.start_line(self.config.base_indent);
+        mem::take(&mut self.out)
     }
 
     pub fn scan_begin(&mut self, token: BeginToken) {
@@ -126,6 +160,9 @@ impl Printer {
                             self.scan_stack.pop_back();
                             self.scan_stack.pop_back();
                             self.right_total -= break_token.blank_space as isize;
+                            if break_token.if_nonempty {
+                                self.empty_block_interior();
+                            }
                             return;
                         }
                     }
@@ -133,6 +170,7 @@ impl Printer {
                         self.buf.pop_last();
                         self.scan_stack.pop_back();
                         self.right_total -= break_token.blank_space as isize;
+                        self.empty_block_interior();
                     }
                 }
             }
@@ -145,6 +183,12 @@ impl Printer {
     }
 
     pub fn scan_break(&mut self, token: BreakToken) {
+        if let Some(index) = self.pending_hanging.take() {
+            if let Some(position) = self.scan_stack.iter().position(|&i| i == index) {
+                self.scan_stack.remove(position);
+                self.buf[index].size += self.right_total;
+            }
+        }
         if self.scan_stack.is_empty() {
             self.left_total = 1;
             self.right_total = 1;
@@ -158,13 +202,16 @@ impl Printer {
         });
         self.scan_stack.push_back(right);
         self.right_total += token.blank_space as isize;
+        if token.hanging {
+            self.pending_hanging = Some(right);
+        }
     }
 
     pub fn scan_string(&mut self, string: Cow<'static, str>) {
         if self.scan_stack.is_empty() {
             self.print_string(string);
         } else {
-            let len = string.len() as isize;
+            let len = color::display_len(&string);
             self.buf.push(BufEntry {
                 token: Token::String(string),
                 size: len,
@@ -182,8 +229,10 @@ impl Printer {
         }
     }
 
+    #[cfg(feature = "full")]
     pub fn end_with_max_width(&mut self, max: isize) {
         let mut depth = 1;
+        let mut too_wide = false;
         for &index in self.scan_stack.iter().rev() {
             let entry = &self.buf[index];
             match entry.token {
@@ -192,13 +241,7 @@ impl Printer {
                     if depth == 0 {
                         if entry.size < 0 {
                             let actual_width = entry.size + self.right_total;
-                            if actual_width > max {
-                                self.buf.push(BufEntry {
-                                    token: Token::String(Cow::Borrowed("")),
-                                    size: SIZE_INFINITY,
-                                });
-                                self.right_total += SIZE_INFINITY;
-                            }
+                            too_wide = actual_width > max;
                         }
                         break;
                     }
@@ -208,6 +251,20 @@ impl Printer {
                 Token::String(_) => unreachable!(),
             }
         }
+        if too_wide {
+            // Measure whatever box the group ends with on its own first, so
+            // that only this group is forced to break, not for example the
+            // arguments of the last call in a method chain.
+            self.scan_break(BreakToken {
+                never_break: true,
+                ..BreakToken::default()
+            });
+            self.buf.push(BufEntry {
+                token: Token::String(Cow::Borrowed("")),
+                size: SIZE_INFINITY,
+            });
+            self.right_total += SIZE_INFINITY;
+        }
         self.scan_end();
     }
 
@@ -251,7 +308,7 @@ impl Printer {
 
     fn check_stack(&mut self, mut depth: usize) {
         while let Some(&index) = self.scan_stack.back() {
-            let mut entry = &mut self.buf[index];
+            let entry = &mut self.buf[index];
             match entry.token {
                 Token::Begin(_) => {
                     if depth == 0 {
@@ -293,8 +350,7 @@ impl Printer {
                 self.out
                     .extend(token.offset.to_string().chars().map(|ch| match ch {
                         '0'..='9' => ['₀', '₁', '₂', '₃', '₄', '₅', '₆', '₇', '₈', '₉']
-                            [(ch as u8 - b'0') as usize]
-                            as char,
+                            [(ch as u8 - b'0') as usize],
                         '-' => '₋',
                         _ => unreachable!(),
                     }));
@@ -350,9 +406,12 @@ impl Printer {
                 self.out.push('·');
             }
             self.out.push('\n');
-            let indent = self.indent as isize + token.offset;
-            self.pending_indentation = usize::try_from(indent).unwrap();
-            self.space = cmp::max(MARGIN - indent, MIN_SPACE);
+            if token.hanging {
+                self.indent = usize::try_from(self.indent as isize + token.offset).unwrap();
+                self.start_line(self.indent);
+            } else {
+                self.start_line(usize::try_from(self.indent as isize + token.offset).unwrap());
+            }
             if let Some(post_break) = token.post_break {
                 self.print_indent();
                 self.out.push(post_break);
@@ -361,16 +420,34 @@ impl Printer {
         }
     }
 
+    // Buffers the indentation of a new line and resets the space left on it.
+    fn start_line(&mut self, indent: usize) {
+        let width = if self.config.use_tabs {
+            self.pending_tabs = indent / INDENT as usize;
+            self.pending_indentation = indent % INDENT as usize;
+            self.pending_tabs * self.config.tab_width + self.pending_indentation
+        } else {
+            self.pending_tabs = 0;
+            self.pending_indentation = indent;
+            indent
+        };
+        let margin = self.config.max_width as isize;
+        self.space = cmp::max(margin - width as isize, cmp::min(MIN_SPACE, margin));
+    }
+
     fn print_string(&mut self, string: Cow<'static, str>) {
         self.print_indent();
         self.out.push_str(&string);
-        self.space -= string.len() as isize;
+        self.space -= color::display_len(&string);
     }
 
     fn print_indent(&mut self) {
-        self.out.reserve(self.pending_indentation);
+        self.out
+            .reserve(self.pending_tabs + self.pending_indentation);
+        self.out.extend(iter::repeat('\t').take(self.pending_tabs));
         self.out
             .extend(iter::repeat(' ').take(self.pending_indentation));
+        self.pending_tabs = 0;
         self.pending_indentation = 0;
     }
 }
@@ -1,6 +1,9 @@
 use crate::algorithm::Printer;
+use crate::color::Category;
+use crate::iter::IterDelimited;
 use crate::INDENT;
-use proc_macro2::{Delimiter, TokenStream, TokenTree};
+use proc_macro2::{Delimiter, Ident, Literal, TokenStream, TokenTree};
+use std::iter::Peekable;
 use syn::{AttrStyle, Attribute, Lit, PathArguments};
 
 impl Printer {
@@ -12,6 +15,27 @@ impl Printer {
         }
     }
 
+    // A lone attribute other than a doc comment goes on the same line as the
+    // function or field it is attached to if both fit, and the configuration
+    // allows it.
+    pub fn outer_attrs_inline(&mut self, attrs: &[Attribute]) {
+        let mut outer = attrs
+            .iter()
+            .filter(|attr| matches!(attr.style, AttrStyle::Outer));
+        match (outer.next(), outer.next()) {
+            (Some(attr), None)
+                if self.config.inline_attributes
+                    && value_of_attribute("doc", attr).is_none()
+                    && value_of_attribute("comment", attr).is_none() =>
+            {
+                self.ibox(0);
+                self.attr(attr);
+                self.end();
+            }
+            _ => self.outer_attrs(attrs),
+        }
+    }
+
     pub fn inner_attrs(&mut self, attrs: &[Attribute]) {
         for attr in attrs {
             if let AttrStyle::Inner(_) = attr.style {
@@ -21,30 +45,61 @@ impl Printer {
     }
 
     fn attr(&mut self, attr: &Attribute) {
-        if let Some(doc) = value_of_attribute("doc", attr) {
+        let value = value_of_attribute("doc", attr);
+        if self.config.normalize_doc_attributes {
+            if let Some(value) = &value {
+                if value
+                    .split('\n')
+                    .all(|line| doc_line_round_trips(line, attr.style))
+                {
+                    for line in value.split('\n') {
+                        self.highlighted(
+                            Category::Comment,
+                            match attr.style {
+                                AttrStyle::Outer => "///",
+                                AttrStyle::Inner(_) => "//!",
+                            },
+                        );
+                        self.highlighted(Category::Comment, line.to_owned());
+                        self.hardbreak();
+                    }
+                    return;
+                }
+            }
+        }
+        let doc = value
+            .clone()
+            .filter(|doc| doc_comment_round_trips(doc, attr.style));
+        if let Some(doc) = doc {
             if doc.contains('\n') {
-                self.word(match attr.style {
-                    AttrStyle::Outer => "/**",
-                    AttrStyle::Inner(_) => "/*!",
-                });
-                self.word(doc);
-                self.word("*/");
+                self.highlighted(
+                    Category::Comment,
+                    match attr.style {
+                        AttrStyle::Outer => "/**",
+                        AttrStyle::Inner(_) => "/*!",
+                    },
+                );
+                self.highlighted(Category::Comment, doc);
+                self.highlighted(Category::Comment, "*/");
             } else {
-                self.word(match attr.style {
-                    AttrStyle::Outer => "///",
-                    AttrStyle::Inner(_) => "//!",
-                });
-                self.word(doc);
+                self.highlighted(
+                    Category::Comment,
+                    match attr.style {
+                        AttrStyle::Outer => "///",
+                        AttrStyle::Inner(_) => "//!",
+                    },
+                );
+                self.highlighted(Category::Comment, doc);
             }
             self.hardbreak();
         } else if let Some(comment) = value_of_attribute("comment", attr) {
             if comment.contains('\n') {
-                self.word("/*");
-                self.word(comment);
-                self.word("*/");
+                self.highlighted(Category::Comment, "/*");
+                self.highlighted(Category::Comment, comment);
+                self.highlighted(Category::Comment, "*/");
             } else {
-                self.word("//");
-                self.word(comment);
+                self.highlighted(Category::Comment, "//");
+                self.highlighted(Category::Comment, comment);
             }
             self.hardbreak();
         } else {
@@ -53,13 +108,64 @@ impl Printer {
                 AttrStyle::Inner(_) => "#!",
             });
             self.word("[");
+            let wrap_unsafe = self.config.unsafe_attributes && requires_unsafe(attr);
+            if wrap_unsafe {
+                self.word("unsafe(");
+            }
             self.path(&attr.path);
-            self.attr_tokens(attr.tokens.clone());
+            match value {
+                Some(value) if self.config.normalize_doc_attributes => {
+                    self.word(" = ");
+                    self.highlighted(Category::Literal, escape_doc(&value));
+                }
+                _ => match cfg_predicate_of_attribute(attr) {
+                    Some(predicate) => {
+                        self.word("(");
+                        self.cfg_predicate(&predicate);
+                        self.word(")");
+                    }
+                    None => self.attr_tokens(attr.tokens.clone()),
+                },
+            }
+            if wrap_unsafe {
+                self.word(")");
+            }
             self.word("]");
             self.space();
         }
     }
 
+    // A list of two or more predicates breaks one per line when it doesn't
+    // fit, while a single predicate hugs the parentheses around it, as in
+    // `not(any(...))`.
+    fn cfg_predicate(&mut self, predicate: &CfgPredicate) {
+        match predicate {
+            CfgPredicate::Option(name) => self.ident(name),
+            CfgPredicate::KeyValue(name, value) => {
+                self.ident(name);
+                self.word(" = ");
+                self.token_literal(value);
+            }
+            CfgPredicate::List(name, predicates) => {
+                self.ident(name);
+                self.word("(");
+                if predicates.len() == 1 {
+                    self.cfg_predicate(&predicates[0]);
+                } else if !predicates.is_empty() {
+                    self.cbox(INDENT);
+                    self.zerobreak();
+                    for predicate in predicates.iter().delimited() {
+                        self.cfg_predicate(&predicate);
+                        self.trailing_comma(predicate.is_last);
+                    }
+                    self.offset(-INDENT);
+                    self.end();
+                }
+                self.word(")");
+            }
+        }
+    }
+
     fn attr_tokens(&mut self, tokens: TokenStream) {
         let mut stack = Vec::new();
         stack.push((tokens.into_iter(), Delimiter::None));
@@ -83,13 +189,17 @@ impl Printer {
                     state = Word;
                 }
                 Some(TokenTree::Punct(punct)) => {
+                    // A list of `key = value` arguments, as in `#[deprecated]`,
+                    // only breaks after a comma, never around the `=`.
                     let ch = punct.as_char();
                     if let (Word, '=') = (state, ch) {
-                        space(self);
+                        self.nbsp();
                     }
                     self.token_punct(ch);
-                    if let '=' | ',' = ch {
-                        space(self);
+                    match ch {
+                        '=' => self.nbsp(),
+                        ',' => space(self),
+                        _ => {}
                     }
                     state = Punct;
                 }
@@ -152,6 +262,142 @@ impl Printer {
     }
 }
 
+// Whether the doc comment printed for this `#[doc = "..."]` would be lexed
+// back into the same attribute. For example `#[doc = "/x"]` can't be written
+// as `////x`, which is an ordinary comment, and `#[doc = "/x\ny"]` can't be
+// written as `/**/x\ny*/`, which starts with an empty comment.
+fn doc_comment_round_trips(doc: &str, style: AttrStyle) -> bool {
+    let multiline = doc.contains('\n');
+    if multiline && (doc.contains("/*") || doc.contains("*/")) {
+        return false;
+    }
+    match style {
+        AttrStyle::Outer if multiline => !doc.starts_with(&['*', '/'][..]),
+        AttrStyle::Outer => !doc.starts_with('/'),
+        AttrStyle::Inner(_) => true,
+    }
+}
+
+// Whether one line of a doc comment, printed as its own `///` or `//!`
+// comment, would be lexed back into the same text.
+fn doc_line_round_trips(line: &str, style: AttrStyle) -> bool {
+    !line.contains('\r') && doc_comment_round_trips(line, style)
+}
+
+// String literal for the value of a doc attribute that can't be printed as a
+// comment, escaping only quotes, backslashes, and control characters.
+fn escape_doc(value: &str) -> String {
+    let mut escaped = String::from("\"");
+    for ch in value.chars() {
+        match ch {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            ch if ch.is_control() => escaped.extend(ch.escape_unicode()),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+enum CfgPredicate {
+    Option(Ident),
+    KeyValue(Ident, Literal),
+    List(Ident, Vec<CfgPredicate>),
+}
+
+// The predicate of a `#[cfg(...)]` attribute, if it is made of nothing but
+// `name`, `name = "value"` and `name(...)` predicates.
+fn cfg_predicate_of_attribute(attr: &Attribute) -> Option<CfgPredicate> {
+    let is_cfg = attr.path.leading_colon.is_none()
+        && attr.path.segments.len() == 1
+        && matches!(attr.path.segments[0].arguments, PathArguments::None)
+        && attr.path.segments[0].ident == "cfg";
+    if !is_cfg {
+        return None;
+    }
+    let mut tokens = attr.tokens.clone().into_iter();
+    let group = match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Group(group)), None) if group.delimiter() == Delimiter::Parenthesis => {
+            group
+        }
+        _ => return None,
+    };
+    let mut predicates = cfg_predicate_list(group.stream())?;
+    if predicates.len() == 1 {
+        predicates.pop()
+    } else {
+        None
+    }
+}
+
+fn cfg_predicate_list(tokens: TokenStream) -> Option<Vec<CfgPredicate>> {
+    let mut tokens = tokens.into_iter().peekable();
+    let mut predicates = Vec::new();
+    while tokens.peek().is_some() {
+        predicates.push(cfg_predicate(&mut tokens)?);
+        match tokens.next() {
+            None => break,
+            Some(TokenTree::Punct(punct)) if punct.as_char() == ',' => {}
+            Some(_) => return None,
+        }
+    }
+    Some(predicates)
+}
+
+fn cfg_predicate(tokens: &mut Peekable<impl Iterator<Item = TokenTree>>) -> Option<CfgPredicate> {
+    let name = match tokens.next() {
+        Some(TokenTree::Ident(name)) => name,
+        _ => return None,
+    };
+    match tokens.peek() {
+        Some(TokenTree::Punct(punct)) if punct.as_char() == '=' => {
+            tokens.next();
+            match tokens.next() {
+                Some(TokenTree::Literal(value)) => Some(CfgPredicate::KeyValue(name, value)),
+                _ => None,
+            }
+        }
+        Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => {
+            let predicates = cfg_predicate_list(group.stream())?;
+            tokens.next();
+            Some(CfgPredicate::List(name, predicates))
+        }
+        _ => Some(CfgPredicate::Option(name)),
+    }
+}
+
+// Attributes that must be written inside `unsafe(...)` as of Rust 2024.
+fn requires_unsafe(attr: &Attribute) -> bool {
+    attr.path.leading_colon.is_none()
+        && attr.path.segments.len() == 1
+        && matches!(attr.path.segments[0].arguments, PathArguments::None)
+        && ["export_name", "link_section", "no_mangle"]
+            .iter()
+            .any(|name| attr.path.segments[0].ident == name)
+}
+
+#[cfg(feature = "full")]
+pub fn is_cfg_gated(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path.leading_colon.is_none()
+            && attr.path.segments.len() == 1
+            && matches!(attr.path.segments[0].arguments, PathArguments::None)
+            && (attr.path.segments[0].ident == "cfg" || attr.path.segments[0].ident == "cfg_attr")
+    })
+}
+
+#[cfg(feature = "full")]
+pub fn has_inner(attrs: &[Attribute]) -> bool {
+    attrs
+        .iter()
+        .any(|attr| matches!(attr.style, AttrStyle::Inner(_)))
+}
+
 fn value_of_attribute(requested: &str, attr: &Attribute) -> Option<String> {
     let is_doc = attr.path.leading_colon.is_none()
         && attr.path.segments.len() == 1
@@ -0,0 +1,206 @@
+use crate::algorithm::Printer;
+use std::borrow::Cow;
+
+const RESET: &str = "\x1b[0m";
+
+/// One of the eight standard ANSI terminal colors.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn escape(self) -> &'static str {
+        match self {
+            Color::Black => "\x1b[30m",
+            Color::Red => "\x1b[31m",
+            Color::Green => "\x1b[32m",
+            Color::Yellow => "\x1b[33m",
+            Color::Blue => "\x1b[34m",
+            Color::Magenta => "\x1b[35m",
+            Color::Cyan => "\x1b[36m",
+            Color::White => "\x1b[37m",
+        }
+    }
+}
+
+/// Colors used by [`Config::color`](crate::Config::color) for each category of
+/// token. A category set to `None` is printed without escape codes.
+///
+/// The default colors keywords magenta, literals green, and comments blue,
+/// and leaves identifiers uncolored.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ColorScheme {
+    keyword: Option<Color>,
+    literal: Option<Color>,
+    ident: Option<Color>,
+    comment: Option<Color>,
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        ColorScheme {
+            keyword: Some(Color::Magenta),
+            literal: Some(Color::Green),
+            ident: None,
+            comment: Some(Color::Blue),
+        }
+    }
+}
+
+impl ColorScheme {
+    pub fn new() -> Self {
+        ColorScheme::default()
+    }
+
+    /// Keywords such as `fn`, `pub`, `self`, and `where`. Identifiers spelled
+    /// like a weak keyword, such as a field named `default`, aren't keywords.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{Color, ColorScheme, Config};
+    ///
+    /// let src = "union U { default: u8 } fn f() -> Box<dyn T> { m!(union try) }";
+    /// let file = syn::parse_file(src).unwrap();
+    /// let scheme = ColorScheme::new().keyword(Some(Color::Red));
+    /// let colored = prettyplease::unparse_with(&file, &Config::new().color(scheme));
+    /// let keyword = |word| format!("\x1b[31m{}\x1b[0m", word);
+    /// assert!(colored.contains(&format!("{} U {{", keyword("union"))));
+    /// assert!(colored.contains("    default: u8,"));
+    /// assert!(colored.contains(&format!("Box<{} T>", keyword("dyn"))));
+    /// assert!(colored.contains(&format!("m!(union {})", keyword("try"))));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn keyword(mut self, color: Option<Color>) -> Self {
+        self.keyword = color;
+        self
+    }
+
+    /// String, character, numeric, and boolean literals.
+    #[must_use]
+    pub fn literal(mut self, color: Option<Color>) -> Self {
+        self.literal = color;
+        self
+    }
+
+    /// Identifiers and lifetimes.
+    #[must_use]
+    pub fn ident(mut self, color: Option<Color>) -> Self {
+        self.ident = color;
+        self
+    }
+
+    /// Doc comments and `#[comment]` attributes printed as comments.
+    #[must_use]
+    pub fn comment(mut self, color: Option<Color>) -> Self {
+        self.comment = color;
+        self
+    }
+}
+
+#[derive(Copy, Clone)]
+pub enum Category {
+    Keyword,
+    Literal,
+    Ident,
+    Comment,
+}
+
+impl Printer {
+    pub fn highlighted<S: Into<Cow<'static, str>>>(&mut self, category: Category, wrd: S) {
+        let wrd = wrd.into();
+        let scheme = match &self.config.color {
+            Some(scheme) => scheme,
+            None => return self.scan_string(wrd),
+        };
+        let color = match category {
+            Category::Keyword => scheme.keyword,
+            Category::Literal => scheme.literal,
+            Category::Ident => scheme.ident,
+            Category::Comment => scheme.comment,
+        };
+        match color {
+            Some(color) => {
+                self.scan_string(Cow::Owned(format!("{}{}{}", color.escape(), wrd, RESET)));
+            }
+            None => self.scan_string(wrd),
+        }
+    }
+
+    // Words other than identifiers, literals, and comments are made up of
+    // punctuation and keywords, such as "pub(" or " as ". Color the keywords,
+    // including weak keywords like `union`, which the printer only writes
+    // where they are keywords.
+    pub fn highlight_keywords(&mut self, wrd: Cow<'static, str>) {
+        let color = match self.config.color.and_then(|scheme| scheme.keyword) {
+            Some(color) => color,
+            None => return self.scan_string(wrd),
+        };
+        let mut highlighted = String::new();
+        let mut any_keyword = false;
+        let mut rest = &*wrd;
+        while let Some(start) = rest.find(|ch: char| ch.is_ascii_alphabetic()) {
+            let len = rest[start..]
+                .find(|ch: char| !ch.is_ascii_alphanumeric() && ch != '_')
+                .unwrap_or(rest.len() - start);
+            let word = &rest[start..start + len];
+            highlighted.push_str(&rest[..start]);
+            if is_keyword(word) || WEAK_KEYWORDS.contains(&word) {
+                highlighted.push_str(color.escape());
+                highlighted.push_str(word);
+                highlighted.push_str(RESET);
+                any_keyword = true;
+            } else {
+                highlighted.push_str(word);
+            }
+            rest = &rest[start + len..];
+        }
+        if !any_keyword {
+            return self.scan_string(wrd);
+        }
+        highlighted.push_str(rest);
+        self.scan_string(Cow::Owned(highlighted));
+    }
+}
+
+// Keywords only in certain positions, so an identifier spelled like one is
+// still colored as an identifier.
+const WEAK_KEYWORDS: &[&str] = &["auto", "default", "macro_rules", "union", "yeet"];
+
+// The strict and reserved keywords, which can't be identifiers. Identifiers
+// spelled like one are colored as keywords: `self`, `Self`, `super`, `crate`
+// in paths, and keywords showing up as tokens inside macro invocations.
+pub fn is_keyword(ident: &str) -> bool {
+    match ident {
+        "abstract" | "as" | "async" | "await" | "become" | "box" | "break" | "const"
+        | "continue" | "crate" | "do" | "dyn" | "else" | "enum" | "extern" | "false" | "final"
+        | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "macro" | "match" | "mod"
+        | "move" | "mut" | "override" | "priv" | "pub" | "ref" | "return" | "self" | "Self"
+        | "static" | "struct" | "super" | "trait" | "true" | "try" | "type" | "typeof"
+        | "unsafe" | "unsized" | "use" | "virtual" | "where" | "while" | "yield" => true,
+        _ => false,
+    }
+}
+
+// Width of a string once printed, not counting the zero-width escape codes
+// inserted by `Config::color`.
+pub fn display_len(string: &str) -> isize {
+    let mut len = string.len();
+    let mut rest = string;
+    while let Some(start) = rest.find('\x1b') {
+        let end = rest[start..]
+            .find('m')
+            .map_or(rest.len(), |m| start + m + 1);
+        len -= end - start;
+        rest = &rest[end..];
+    }
+    len as isize
+}
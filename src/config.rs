@@ -0,0 +1,1030 @@
+use crate::{ColorScheme, MARGIN};
+use std::rc::Rc;
+#[cfg(feature = "full")]
+use syn::File;
+use syn::Lit;
+
+#[cfg(feature = "full")]
+type Transform = Rc<dyn Fn(&mut File)>;
+type LitFilterFn = dyn Fn(&Lit) -> Option<String>;
+type LitFilter = Rc<LitFilterFn>;
+
+/// Options for [`unparse_with`](crate::unparse_with).
+///
+/// `Config::new()` produces exactly the same output as
+/// [`unparse`](crate::unparse). Each option is set by a builder-style method
+/// that consumes and returns the `Config`.
+#[derive(Clone)]
+#[allow(clippy::struct_excessive_bools)] // each bool is an independent option
+pub struct Config {
+    #[cfg(feature = "full")]
+    pub(crate) transforms: Vec<Transform>,
+    pub(crate) empty_block: EmptyBlockStyle,
+    pub(crate) reorder_impl_items: bool,
+    pub(crate) struct_lit_width: usize,
+    pub(crate) color: Option<ColorScheme>,
+    pub(crate) error_recovery: bool,
+    pub(crate) space_before_colon: bool,
+    pub(crate) use_tabs: bool,
+    pub(crate) tab_width: usize,
+    pub(crate) match_arm_blocks: bool,
+    pub(crate) hex_case: HexCase,
+    pub(crate) lit_filter: Option<LitFilter>,
+    pub(crate) force_explicit_abi: bool,
+    pub(crate) normalize_doc_attributes: bool,
+    pub(crate) reformat_string_literals: bool,
+    pub(crate) blank_lines_between_items: usize,
+    pub(crate) reorder_where_predicates: bool,
+    pub(crate) brace_style: BraceStyle,
+    pub(crate) normalize_numeric_suffix_spacing: bool,
+    pub(crate) remove_nested_parens: bool,
+    pub(crate) sort_fields_and_variants: bool,
+    pub(crate) chain_width: Option<usize>,
+    pub(crate) space_inside_struct_braces: bool,
+    pub(crate) unsafe_attributes: bool,
+    pub(crate) reorder_modules: bool,
+    pub(crate) base_indent: usize,
+    pub(crate) snippet: bool,
+    pub(crate) item_order: ItemOrder,
+    pub(crate) preserve_block_expr_parens: bool,
+    pub(crate) inline_attributes: bool,
+    pub(crate) normalize_visibility: bool,
+    pub(crate) max_width: usize,
+    pub(crate) reformat_lifetimes: bool,
+    pub(crate) inline_single_field_structs: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            #[cfg(feature = "full")]
+            transforms: Vec::new(),
+            empty_block: EmptyBlockStyle::Tight,
+            reorder_impl_items: false,
+            struct_lit_width: 34,
+            color: None,
+            error_recovery: true,
+            space_before_colon: false,
+            use_tabs: false,
+            tab_width: 4,
+            match_arm_blocks: false,
+            hex_case: HexCase::Preserve,
+            lit_filter: None,
+            force_explicit_abi: true,
+            normalize_doc_attributes: false,
+            reformat_string_literals: false,
+            blank_lines_between_items: 0,
+            reorder_where_predicates: false,
+            brace_style: BraceStyle::SameLineWhere,
+            normalize_numeric_suffix_spacing: false,
+            remove_nested_parens: false,
+            sort_fields_and_variants: false,
+            chain_width: None,
+            space_inside_struct_braces: true,
+            unsafe_attributes: false,
+            reorder_modules: false,
+            base_indent: 0,
+            snippet: false,
+            item_order: ItemOrder::Preserve,
+            preserve_block_expr_parens: false,
+            inline_attributes: false,
+            normalize_visibility: false,
+            max_width: MARGIN as usize,
+            reformat_lifetimes: false,
+            inline_single_field_structs: false,
+        }
+    }
+}
+
+/// How to print a pair of braces with nothing between them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EmptyBlockStyle {
+    /// `fn f() {}`
+    Tight,
+    /// `fn f() { }`
+    Spaced,
+}
+
+impl Default for EmptyBlockStyle {
+    fn default() -> Self {
+        EmptyBlockStyle::Tight
+    }
+}
+
+/// Where to put the opening brace of the body of an item.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BraceStyle {
+    /// On the same line as the item's header, unless the item has a
+    /// where-clause, after which the brace starts its own line.
+    SameLineWhere,
+    /// Always on its own line, at the indentation of the item.
+    AlwaysNextLine,
+}
+
+impl Default for BraceStyle {
+    fn default() -> Self {
+        BraceStyle::SameLineWhere
+    }
+}
+
+/// Order of the items of a file.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ItemOrder {
+    /// Items in the order they were written.
+    Preserve,
+    /// Imports, then modules, then consts and statics, then types, then
+    /// traits, then impls, then functions. Named items within a group are
+    /// ordered by name, and the rest keep their relative order. Items
+    /// carrying `#[cfg]` or `#[cfg_attr]`, and macro invocations, are left in
+    /// place and items are never moved across them.
+    Canonical,
+}
+
+impl Default for ItemOrder {
+    fn default() -> Self {
+        ItemOrder::Preserve
+    }
+}
+
+/// Letter case of the digits of hexadecimal integer literals.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HexCase {
+    /// Digits as written in the source.
+    Preserve,
+    /// `0xFF`
+    Upper,
+    /// `0xff`
+    Lower,
+}
+
+impl Default for HexCase {
+    fn default() -> Self {
+        HexCase::Preserve
+    }
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config::default()
+    }
+
+    /// Rewrite the syntax tree before printing it.
+    ///
+    /// The transform receives a clone of the input file, so the caller's
+    /// syntax tree is left untouched. Transforms registered by repeated calls
+    /// run in the order they were registered, each one observing the changes
+    /// made by those before it.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use syn::Item;
+    ///
+    /// let config = prettyplease::Config::new().transform(Box::new(|file| {
+    ///     for item in &mut file.items {
+    ///         if let Item::Struct(item) = item {
+    ///             let name = item.ident.to_string().to_uppercase();
+    ///             item.ident = syn::Ident::new(&name, item.ident.span());
+    ///         }
+    ///     }
+    /// }));
+    ///
+    /// let file = syn::parse_file("struct Point { x: i32 }").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "struct POINT {\n    x: i32,\n}\n");
+    /// # }
+    /// ```
+    #[cfg(feature = "full")]
+    #[must_use]
+    pub fn transform(mut self, transform: Box<dyn Fn(&mut File)>) -> Self {
+        self.transforms.push(Rc::from(transform));
+        self
+    }
+
+    /// Spacing inside empty braces of functions, impls, structs, match
+    /// expressions, and every other brace-delimited body. Defaults to
+    /// [`EmptyBlockStyle::Tight`].
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{Config, EmptyBlockStyle};
+    ///
+    /// let file = syn::parse_file("fn f() {} struct S {}").unwrap();
+    /// let config = Config::new().empty_block(EmptyBlockStyle::Spaced);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "fn f() { }\nstruct S { }\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn empty_block(mut self, style: EmptyBlockStyle) -> Self {
+        self.empty_block = style;
+        self
+    }
+
+    /// Sort the associated items of every impl block into consts, then types,
+    /// then methods, each group ordered by name. Items carrying `#[cfg]` or
+    /// `#[cfg_attr]`, and macro invocations, are left in place and items are
+    /// never moved across them. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let src = "\
+    /// impl S {
+    ///     fn b() {}
+    ///     type T = u8;
+    ///     const C: u8 = 0;
+    ///     #[cfg(test)]
+    ///     fn t() {}
+    ///     fn a() {}
+    ///     const B: u8 = 1;
+    /// }
+    /// ";
+    /// let file = syn::parse_file(src).unwrap();
+    /// let config = Config::new().reorder_impl_items(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "\
+    /// impl S {
+    ///     const C: u8 = 0;
+    ///     type T = u8;
+    ///     fn b() {}
+    ///     #[cfg(test)]
+    ///     fn t() {}
+    ///     const B: u8 = 1;
+    ///     fn a() {}
+    /// }
+    /// ",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reorder_impl_items(mut self, enable: bool) -> Self {
+        self.reorder_impl_items = enable;
+        self
+    }
+
+    /// Maximum width of a struct literal kept on one line, like rustfmt's
+    /// `struct_lit_width`. A wider literal is broken one field per line even
+    /// if it would fit within the line. Defaults to 34.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("fn f() { let p = Point { x: 1, y: 2 }; }").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &Config::new());
+    /// assert_eq!(formatted, "fn f() {\n    let p = Point { x: 1, y: 2 };\n}\n");
+    ///
+    /// let config = Config::new().struct_lit_width(10);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "fn f() {\n    let p = Point {\n        x: 1,\n        y: 2,\n    };\n}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn struct_lit_width(mut self, width: usize) -> Self {
+        self.struct_lit_width = width;
+        self
+    }
+
+    /// Wrap keywords, literals, identifiers, and comments in ANSI color
+    /// escape codes, for printing to a terminal. The escape codes take up no
+    /// width when deciding where to break lines, so stripping them leaves
+    /// exactly the uncolored output. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{Color, ColorScheme, Config};
+    ///
+    /// let scheme = ColorScheme::new().ident(Some(Color::Cyan));
+    /// let file = syn::parse_file("fn main() { let _ = 1; }").unwrap();
+    /// let colored = prettyplease::unparse_with(&file, &Config::new().color(scheme));
+    /// assert!(colored.contains("\x1b[35mfn\x1b[0m \x1b[36mmain\x1b[0m"));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn color(mut self, scheme: ColorScheme) -> Self {
+        self.color = Some(scheme);
+        self
+    }
+
+    /// Repair syntax trees that can't be printed as they are. With this on,
+    /// which is the default, an expression used as a const generic argument
+    /// is wrapped in braces unless it is a literal or a block, because for
+    /// example `T<N + 1>` doesn't parse. Verbatim tokens that syn produces
+    /// for syntax it doesn't model, and that have no dedicated printer, are
+    /// spaced out the way macro bodies are.
+    ///
+    /// With it off, the tree is printed exactly as it is: a path or negated
+    /// literal argument prints bare, as in `T<N>` and `T<-1>`, and any other
+    /// expression that would need braces panics, as does any unsupported
+    /// `Expr`, `Item`, `ForeignItem`, `TraitItem`, `ImplItem`, `Pat` or `Type`
+    /// verbatim.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    /// use std::panic::{self, AssertUnwindSafe};
+    /// use syn::{Item, Type};
+    ///
+    /// let mut file = syn::parse_file("type T = U;").unwrap();
+    /// if let Item::Type(item) = &mut file.items[0] {
+    ///     *item.ty = Type::Verbatim("dyn* Trait".parse().unwrap());
+    /// }
+    /// let formatted = prettyplease::unparse_with(&file, &Config::new());
+    /// assert_eq!(formatted, "type T = dyn * Trait;\n");
+    ///
+    /// let config = Config::new().error_recovery(false);
+    /// let result = panic::catch_unwind(AssertUnwindSafe(|| {
+    ///     prettyplease::unparse_with(&file, &config)
+    /// }));
+    /// assert!(result.is_err());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn error_recovery(mut self, enable: bool) -> Self {
+        self.error_recovery = enable;
+        self
+    }
+
+    /// Print the colon of a type annotation with a space on both sides, as in
+    /// `x : T`. Applies to struct fields, `let` bindings and other patterns
+    /// with a type, function parameters, consts and statics, const generic
+    /// parameters, and associated type constraints like `Item : Clone`.
+    /// Defaults to `x: T`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("struct S { x: u8 } fn f(a: u8) { let b: u8 = a; }").unwrap();
+    /// let config = Config::new().space_before_colon(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "struct S {\n    x : u8,\n}\nfn f(a : u8) {\n    let b : u8 = a;\n}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn space_before_colon(mut self, enable: bool) -> Self {
+        self.space_before_colon = enable;
+        self
+    }
+
+    /// Indent with one tab per level instead of four spaces. Off by default.
+    #[must_use]
+    pub fn use_tabs(mut self, enable: bool) -> Self {
+        self.use_tabs = enable;
+        self
+    }
+
+    /// How many columns an indentation tab counts for when deciding whether a
+    /// line fits, with [`use_tabs`](Config::use_tabs) on. Each level is still
+    /// written as one tab; only where lines break changes. Defaults to 4.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file(
+    ///     "mod a { mod b { mod c { fn f() { if x { match y {
+    ///         _ => call_something(first_argument_value, second_argument_xyz),
+    ///     } } } } } }",
+    /// )
+    /// .unwrap();
+    /// let narrow = prettyplease::unparse_with(&file, &Config::new().use_tabs(true));
+    /// let wide = prettyplease::unparse_with(&file, &Config::new().use_tabs(true).tab_width(8));
+    /// assert_eq!(narrow.replace('\t', "    "), prettyplease::unparse(&file));
+    /// assert!(wide.lines().count() > narrow.lines().count());
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width;
+        self
+    }
+
+    /// Print the body of every match arm inside braces, as in `A => { x }`,
+    /// including arms written without them. No comma follows the closing
+    /// brace. Off by default, which keeps braces only around bodies of more
+    /// than one statement.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("fn f() { match x { A => 1, B => g(), } }").unwrap();
+    /// let config = Config::new().match_arm_blocks(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "fn f() {\n    match x {\n        A => { 1 }\n        B => { g() }\n    }\n}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn match_arm_blocks(mut self, enable: bool) -> Self {
+        self.match_arm_blocks = enable;
+        self
+    }
+
+    /// Letter case of the digits of hexadecimal integer literals. The `0x`
+    /// prefix and any type suffix such as `u32` are unaffected. Defaults to
+    /// [`HexCase::Preserve`].
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{Config, HexCase};
+    ///
+    /// let file = syn::parse_file("const MASK: u32 = 0xDeadBeefu32;").unwrap();
+    /// let config = Config::new().hex_case(HexCase::Upper);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "const MASK: u32 = 0xDEADBEEFu32;\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn hex_case(mut self, case: HexCase) -> Self {
+        self.hex_case = case;
+        self
+    }
+
+    /// Render literals with a custom function. It is called for every literal
+    /// before the default rendering; returning `Some(text)` prints `text` in
+    /// place of the literal and `None` prints the literal as usual.
+    ///
+    /// The returned text is printed verbatim, so it must be a valid literal
+    /// token, otherwise the output will not parse.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use syn::Lit;
+    ///
+    /// let config = prettyplease::Config::new().lit_filter(Box::new(|lit| match lit {
+    ///     Lit::Int(int) if int.suffix() == "u32" => {
+    ///         Some(format!("{:#x}u32", int.base10_parse::<u32>().ok()?))
+    ///     }
+    ///     _ => None,
+    /// }));
+    ///
+    /// let file = syn::parse_file("const MODE: u32 = 493u32;").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "const MODE: u32 = 0x1edu32;\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn lit_filter(mut self, filter: Box<LitFilterFn>) -> Self {
+        self.lit_filter = Some(Rc::from(filter));
+        self
+    }
+
+    /// Print `extern` without an ABI string as `extern "C"`, which is what it
+    /// means, like rustfmt's `force_explicit_abi`. Applies to functions,
+    /// function pointer types, and extern blocks. An ABI written explicitly,
+    /// including `"C"`, always prints as written. On by default; turn it off
+    /// to keep a bare `extern`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("extern fn f() {} extern \"system\" fn g() {}").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &Config::new());
+    /// assert_eq!(formatted, "extern \"C\" fn f() {}\nextern \"system\" fn g() {}\n");
+    ///
+    /// let config = Config::new().force_explicit_abi(false);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "extern fn f() {}\nextern \"system\" fn g() {}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn force_explicit_abi(mut self, enable: bool) -> Self {
+        self.force_explicit_abi = enable;
+        self
+    }
+
+    /// Print every `#[doc = "..."]` by its decoded value, however it was
+    /// written. A multi-line doc becomes one `///` comment per line, an empty
+    /// doc a bare `///`, and a doc that can't be written as comments stays an
+    /// attribute whose string escapes only quotes, backslashes, and control
+    /// characters. Off by default, which prints multi-line docs as `/** */`.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file(r#"
+    ///     #[doc = " Says \"hi\".\n\n Twice."]
+    ///     fn greet() {}
+    /// "#).unwrap();
+    /// let config = prettyplease::Config::new().normalize_doc_attributes(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "/// Says \"hi\".\n///\n/// Twice.\nfn greet() {}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn normalize_doc_attributes(mut self, enable: bool) -> Self {
+        self.normalize_doc_attributes = enable;
+        self
+    }
+
+    /// Replace `\x` and `\u{...}` escapes of printable ASCII characters in
+    /// string, byte string, char, and byte literals by the characters
+    /// themselves, so `"\x41\u{42}"` prints as `"AB"`. Control characters,
+    /// non-ASCII characters, backslashes, and quotes stay escaped, and raw
+    /// strings are untouched, so the value of the literal never changes. Off
+    /// by default, which prints every literal exactly as written.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file(r#"const S: &str = "\x41\u{42}\n\u{e9}";"#).unwrap();
+    /// let config = Config::new().reformat_string_literals(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "const S: &str = \"AB\\n\\u{e9}\";\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reformat_string_literals(mut self, enable: bool) -> Self {
+        self.reformat_string_literals = enable;
+        self
+    }
+
+    /// Number of blank lines printed between consecutive top-level items of
+    /// a file. Syn keeps no record of the blank lines in the original source,
+    /// so there is nothing to pad up to a minimum or cap at a maximum; every
+    /// pair of items gets exactly this many. Defaults to 0.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file("fn a() {} fn b() {} fn c() {}").unwrap();
+    /// let config = prettyplease::Config::new().blank_lines_between_items(2);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "fn a() {}\n\n\nfn b() {}\n\n\nfn c() {}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn blank_lines_between_items(mut self, lines: usize) -> Self {
+        self.blank_lines_between_items = lines;
+        self
+    }
+
+    /// Sort the predicates of every where-clause into lifetime predicates
+    /// first, then type predicates ordered by the bounded type as printed.
+    /// The order of predicates has no meaning, so this never changes what the
+    /// code means. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file("fn f<'a, T, U>() where U: Copy, 'a: 'static, T: Clone {}")
+    ///     .unwrap();
+    /// let config = prettyplease::Config::new().reorder_where_predicates(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "fn f<'a, T, U>()\nwhere\n    'a: 'static,\n    T: Clone,\n    U: Copy,\n{}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reorder_where_predicates(mut self, enable: bool) -> Self {
+        self.reorder_where_predicates = enable;
+        self
+    }
+
+    /// Placement of the opening brace of functions, impls, structs, enums,
+    /// unions, modules, and traits, including associated functions. Defaults
+    /// to [`BraceStyle::SameLineWhere`].
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{BraceStyle, Config};
+    ///
+    /// let file = syn::parse_file("impl S { fn f() { g(); } }").unwrap();
+    /// let config = Config::new().brace_style(BraceStyle::AlwaysNextLine);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "impl S\n{\n    fn f()\n    {\n        g();\n    }\n}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn brace_style(mut self, style: BraceStyle) -> Self {
+        self.brace_style = style;
+        self
+    }
+
+    /// Print the type suffix of integer and float literals lowercase and
+    /// directly after the digits, so `1_u8` and `1U8` print as `1u8`. The
+    /// digits themselves, including those of hex literals, are unchanged.
+    /// Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("const N: [u32; 3] = [1_u32, 0x1FU32, 2.5_F32 as u32];").unwrap();
+    /// let config = Config::new().normalize_numeric_suffix_spacing(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "const N: [u32; 3] = [1u32, 0x1Fu32, 2.5f32 as u32];\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn normalize_numeric_suffix_spacing(mut self, enable: bool) -> Self {
+        self.normalize_numeric_suffix_spacing = enable;
+        self
+    }
+
+    /// Drop parentheses around types and patterns that don't need them, so
+    /// `&((i32))` prints as `&i32` and `((x))` as `x`. Parentheses around a
+    /// trait object or `impl Trait` with more than one bound are kept, as in
+    /// `&(dyn A + B)`, and so are those around an or-pattern or range
+    /// pattern, as in `&(a | b)`, and around a binding with `ref`, `mut` or
+    /// `@` after `&`, `box` or `@`, as in `&(mut x)`. Off by default, which
+    /// prints every parenthesized type and pattern as written.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("type T = (&((i32)), &(dyn A + B));").unwrap();
+    /// let config = Config::new().remove_nested_parens(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "type T = (&i32, &(dyn A + B));\n");
+    ///
+    /// let file = syn::parse_file("fn f() { let ((x)) = Some((a | b)); if let &(a | b) = y {} }").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert!(formatted.contains("let x = Some((a | b));"));
+    /// assert!(formatted.contains("if let &(a | b) = y {}"));
+    ///
+    /// let file = syn::parse_file("fn f((mut y): u8, &(mut z): &u8) { if let Some((a | b)) = y {} }").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert!(formatted.starts_with("fn f(mut y: u8, &(mut z): &u8) {"));
+    /// assert!(formatted.contains("if let Some(a | b) = y {}"));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn remove_nested_parens(mut self, enable: bool) -> Self {
+        self.remove_nested_parens = enable;
+        self
+    }
+
+    /// Sort the named fields of every struct, union and struct variant, and
+    /// the variants of every enum, by name. Attributes and doc comments move
+    /// along with their field or variant. An enum in which any variant has
+    /// an explicit discriminant keeps its variants in declaration order.
+    /// Tuple fields are never reordered. Off by default.
+    ///
+    /// Sorting changes the declaration order seen by derives such as
+    /// `PartialOrd` and by `#[repr(C)]` layout, so this is meant for output
+    /// that is read rather than compiled, like API listings.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("enum E { B, A } enum F { B = 1, A = 0 }").unwrap();
+    /// let config = Config::new().sort_fields_and_variants(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "enum E {\n    A,\n    B,\n}\nenum F {\n    B = 1,\n    A = 0,\n}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn sort_fields_and_variants(mut self, enable: bool) -> Self {
+        self.sort_fields_and_variants = enable;
+        self
+    }
+
+    /// Maximum width of a method chain of two or more links kept on one line,
+    /// like rustfmt's `chain_width`. A wider chain is broken before every `.`
+    /// even if it would fit within the line. Defaults to the full line width
+    /// set by [`max_width`](Config::max_width), so chains only break when they
+    /// have to.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("fn f() { values.iter().map(double).fold(0, add) }").unwrap();
+    /// let config = Config::new().chain_width(20);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "fn f() {\n    values\n        .iter()\n        .map(double)\n        .fold(0, add)\n}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn chain_width(mut self, width: usize) -> Self {
+        self.chain_width = Some(width);
+        self
+    }
+
+    /// Put a space just inside the braces of struct literals and struct
+    /// patterns that fit on one line, as in `Point { x, y }`. Turning this off
+    /// prints `Point {x, y}`. Struct definitions are unaffected. On by
+    /// default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("fn f() { let P { x, .. } = P { x: 1, ..p }; }").unwrap();
+    /// let config = Config::new().space_inside_struct_braces(false);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "fn f() {\n    let P {x, ..} = P {x: 1, ..p};\n}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn space_inside_struct_braces(mut self, enable: bool) -> Self {
+        self.space_inside_struct_braces = enable;
+        self
+    }
+
+    /// Wrap the attributes that Rust 2024 requires to be marked unsafe,
+    /// `#[export_name]`, `#[link_section]` and `#[no_mangle]`, as in
+    /// `#[unsafe(no_mangle)]`. Attributes already written that way are
+    /// printed as they are. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("#[no_mangle] extern \"C\" fn f() {}").unwrap();
+    /// let config = Config::new().unsafe_attributes(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "#[unsafe(no_mangle)]\nextern \"C\" fn f() {}\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn unsafe_attributes(mut self, enable: bool) -> Self {
+        self.unsafe_attributes = enable;
+        self
+    }
+
+    /// Sort the items of every inline module in the order of
+    /// [`ItemOrder::Canonical`]. Combine with
+    /// [`item_order`](Config::item_order) to also sort the items of the file,
+    /// and with [`reorder_impl_items`](Config::reorder_impl_items) to sort the
+    /// contents of impl blocks. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("mod m { fn f() {} struct S; const C: u8 = 0; use x::y; }").unwrap();
+    /// let config = Config::new().reorder_modules(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "mod m {\n    use x::y;\n    const C: u8 = 0;\n    struct S;\n    fn f() {}\n}\n",
+    /// );
+    ///
+    /// // Items aren't moved across a `#[cfg]` item.
+    /// let file = syn::parse_file("mod m { fn f() {} use x::y; #[cfg(test)] mod tests {} struct S; use x::z; }").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "mod m {\n    use x::y;\n    fn f() {}\n    #[cfg(test)]\n    mod tests {}\n    use x::z;\n    struct S;\n}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reorder_modules(mut self, enable: bool) -> Self {
+        self.reorder_modules = enable;
+        self
+    }
+
+    /// Indent every line of output by this many columns, for embedding the
+    /// code in a surrounding context. With [`use_tabs`](Config::use_tabs),
+    /// each full indentation level of the base indent is written as a tab.
+    /// Defaults to 0.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("fn f() { g(); }").unwrap();
+    /// let config = Config::new().base_indent(8);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "        fn f() {\n            g();\n        }\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn base_indent(mut self, columns: usize) -> Self {
+        self.base_indent = columns;
+        self
+    }
+
+    /// Leave off the newline that otherwise ends the output, for embedding
+    /// a fragment in documentation or an error message. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("struct S;").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &Config::new().snippet(true));
+    /// assert_eq!(formatted, "struct S;");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn snippet(mut self, enable: bool) -> Self {
+        self.snippet = enable;
+        self
+    }
+
+    /// Order of the top-level items of the file. Defaults to
+    /// [`ItemOrder::Preserve`].
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::{Config, ItemOrder};
+    ///
+    /// let file = syn::parse_file("fn main() {} impl S {} struct S; use std::fmt;").unwrap();
+    /// let config = Config::new().item_order(ItemOrder::Canonical);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "use std::fmt;\nstruct S;\nimpl S {}\nfn main() {}\n");
+    ///
+    /// let again = prettyplease::unparse_with(&syn::parse_file(&formatted).unwrap(), &config);
+    /// assert_eq!(again, formatted);
+    ///
+    /// // Items aren't moved across a `#[cfg]` item.
+    /// let file = syn::parse_file("fn main() {} use a::b; #[cfg(test)] mod tests {} fn g() {} struct T;").unwrap();
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(
+    ///     formatted,
+    ///     "use a::b;\nfn main() {}\n#[cfg(test)]\nmod tests {}\nstruct T;\nfn g() {}\n",
+    /// );
+    ///
+    /// let again = prettyplease::unparse_with(&syn::parse_file(&formatted).unwrap(), &config);
+    /// assert_eq!(again, formatted);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn item_order(mut self, order: ItemOrder) -> Self {
+        self.item_order = order;
+        self
+    }
+
+    /// Print an expression statement that begins with a block, `if`, `match`
+    /// or loop exactly as the syntax tree has it. By default that leading
+    /// expression is put in parentheses when the syntax tree doesn't already
+    /// have them and it is followed by anything other than a method call,
+    /// field access, `.await` or `?`, since `{ 1 } + 2;` would parse as the
+    /// block followed by a separate statement `+2`. Parentheses that are in
+    /// the syntax tree are printed either way. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let expr: syn::Expr = syn::parse_str("{ 1 } + 2").unwrap();
+    /// let stmt = syn::Stmt::Semi(expr, Default::default());
+    /// assert_eq!(prettyplease::unparse_stmt(&stmt), "({ 1 }) + 2;\n");
+    ///
+    /// let file = syn::parse_file("fn f() { (match x { _ => 1 }).to_string(); }").unwrap();
+    /// let formatted = prettyplease::unparse(&file);
+    /// assert!(formatted.contains("(match x {"));
+    /// assert_eq!(prettyplease::unparse(&syn::parse_file(&formatted).unwrap()), formatted);
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn preserve_block_expr_parens(mut self, preserve: bool) -> Self {
+        self.preserve_block_expr_parens = preserve;
+        self
+    }
+
+    /// Put the attribute of a function or field on the same line as it, when
+    /// there is a single attribute other than a doc comment and the function
+    /// or field fits on that line along with it. Off by default, so that
+    /// every attribute goes on its own line.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file("#[inline] fn f() {} #[inline] #[must_use] fn g() {}").unwrap();
+    /// let config = prettyplease::Config::new().inline_attributes(true);
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     "#[inline] fn f() {}\n#[inline]\n#[must_use]\nfn g() {}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn inline_attributes(mut self, inline: bool) -> Self {
+        self.inline_attributes = inline;
+        self
+    }
+
+    /// Print the equivalent spellings of a visibility the same way:
+    /// `pub(in crate)` and the unstable `crate` as `pub(crate)`, and
+    /// `pub(in self)` and `pub(in super)` as `pub(self)` and `pub(super)`.
+    /// Visibilities restricted to some other path, like
+    /// `pub(in crate::module)`, are left as written. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file(
+    ///     "pub(in crate) fn a() {} pub(in self) fn b() {} pub(in super) fn c() {} pub(in crate::m) fn d() {}",
+    /// )
+    /// .unwrap();
+    /// let config = prettyplease::Config::new().normalize_visibility(true);
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     "pub(crate) fn a() {}\npub(self) fn b() {}\npub(super) fn c() {}\npub(in crate::m) fn d() {}\n",
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn normalize_visibility(mut self, enable: bool) -> Self {
+        self.normalize_visibility = enable;
+        self
+    }
+
+    /// Width of the lines the printer tries to stay within. Defaults to 89.
+    /// A line can still come out wider when there is nowhere to break it;
+    /// [`unparse_checked`](crate::unparse_checked) reports such lines. Method
+    /// chains may use the whole width unless a narrower
+    /// [`chain_width`](Config::chain_width) is set.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file("fn f() { g(first_argument, second_argument); }").unwrap();
+    /// let config = prettyplease::Config::new().max_width(30);
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     "fn f() {\n    g(\n        first_argument,\n        second_argument,\n    );\n}\n",
+    /// );
+    ///
+    /// let chain = "receiver_object.first_method(argument_one).second_method(argument_two).third_method(arg_xxxxxxx)";
+    /// let file = syn::parse_file(&format!("fn f() {{ {}; }}", chain)).unwrap();
+    /// let config = prettyplease::Config::new().max_width(120);
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     format!("fn f() {{\n    {};\n}}\n", chain),
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = width;
+        self
+    }
+
+    /// Drop a lifetime parameter of a function that is named nowhere but as
+    /// the lifetime of a single reference argument, printing that reference
+    /// with the anonymous lifetime `'_` instead, which means the same thing.
+    /// Lifetimes with bounds, or named anywhere else in the signature or
+    /// body, are left alone. Off by default.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// let file = syn::parse_file("fn f<'a, 'b>(x: &'a str, y: &'b str) -> &'b str { y }").unwrap();
+    /// let config = prettyplease::Config::new().reformat_lifetimes(true);
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     "fn f<'b>(x: &'_ str, y: &'b str) -> &'b str {\n    y\n}\n",
+    /// );
+    ///
+    /// let src = "\
+    /// fn g<'a, T>(x: &'a T) {}
+    /// fn h<'a, T>(x: &'a T) {
+    ///     let y: &'a T = x;
+    /// }
+    /// fn m<'a, T>(x: &'a T) {
+    ///     m!('a);
+    /// }
+    /// ";
+    /// let file = syn::parse_file(src).unwrap();
+    /// assert_eq!(
+    ///     prettyplease::unparse_with(&file, &config),
+    ///     src.replacen("fn g<'a, T>(x: &'a T)", "fn g<T>(x: &'_ T)", 1),
+    /// );
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn reformat_lifetimes(mut self, enable: bool) -> Self {
+        self.reformat_lifetimes = enable;
+        self
+    }
+
+    /// Keep a struct or union with a single named field on one line when it
+    /// fits, as in `struct S { field: T }`, the way tuple structs such as
+    /// `struct Wrapper(pub Inner);` already print. Off by default, which
+    /// always expands braced fields one per line like rustfmt does.
+    ///
+    /// ```
+    /// # #[cfg(feature = "full")] {
+    /// use prettyplease::Config;
+    ///
+    /// let file = syn::parse_file("struct S { field: T } struct Wrapper(pub Inner);").unwrap();
+    /// let config = Config::new().inline_single_field_structs(true);
+    /// let formatted = prettyplease::unparse_with(&file, &config);
+    /// assert_eq!(formatted, "struct S { field: T }\nstruct Wrapper(pub Inner);\n");
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn inline_single_field_structs(mut self, enable: bool) -> Self {
+        self.inline_single_field_structs = enable;
+        self
+    }
+}
@@ -1,4 +1,5 @@
 use crate::algorithm::{self, BeginToken, BreakToken, Breaks, Printer};
+use crate::{BraceStyle, EmptyBlockStyle, INDENT};
 use std::borrow::Cow;
 
 impl Printer {
@@ -22,7 +23,7 @@ impl Printer {
 
     pub fn word<S: Into<Cow<'static, str>>>(&mut self, wrd: S) {
         let s = wrd.into();
-        self.scan_string(s);
+        self.highlight_keywords(s);
     }
 
     fn spaces(&mut self, n: usize) {
@@ -48,6 +49,7 @@ impl Printer {
         self.spaces(algorithm::SIZE_INFINITY as usize);
     }
 
+    #[cfg(feature = "full")]
     pub fn space_if_nonempty(&mut self) {
         self.scan_break(BreakToken {
             blank_space: 1,
@@ -64,6 +66,38 @@ impl Printer {
         });
     }
 
+    // Everything between the braces of a body that turned out to be empty.
+    // Breaks inserted by `space_if_nonempty` and `hardbreak_if_nonempty` are
+    // replaced by this when nothing follows them.
+    pub fn empty_block_interior(&mut self) {
+        match self.config.empty_block {
+            EmptyBlockStyle::Tight => {}
+            EmptyBlockStyle::Spaced => self.word(" "),
+        }
+    }
+
+    // Whatever separates an item's header from the opening brace of its body
+    // when there is no where-clause: a space, or with `BraceStyle::AlwaysNextLine`
+    // a line break back to the item's indentation.
+    pub fn space_before_body_brace(&mut self) {
+        match self.config.brace_style {
+            BraceStyle::SameLineWhere => self.nbsp(),
+            BraceStyle::AlwaysNextLine => {
+                self.hardbreak();
+                self.offset(-INDENT);
+            }
+        }
+    }
+
+    // The colon between a name and its type, as in `x: T`.
+    pub fn type_annotation_colon(&mut self) {
+        self.word(if self.config.space_before_colon {
+            " : "
+        } else {
+            ": "
+        });
+    }
+
     pub fn trailing_comma(&mut self, is_last: bool) {
         if is_last {
             self.scan_break(BreakToken {
@@ -89,6 +123,49 @@ impl Printer {
         }
     }
 
+    // Breaks just inside the braces of a struct literal or struct pattern,
+    // which print as `S { a }`, or as `S {a}` if space_inside_struct_braces
+    // is turned off.
+    #[cfg(feature = "full")]
+    pub fn struct_brace_space_if_nonempty(&mut self) {
+        self.scan_break(BreakToken {
+            blank_space: usize::from(self.config.space_inside_struct_braces),
+            if_nonempty: true,
+            ..BreakToken::default()
+        });
+    }
+
+    #[cfg(feature = "full")]
+    pub fn struct_brace_space(&mut self) {
+        self.spaces(usize::from(self.config.space_inside_struct_braces));
+    }
+
+    #[cfg(feature = "full")]
+    pub fn struct_trailing_comma_or_space(&mut self, is_last: bool) {
+        if is_last {
+            self.scan_break(BreakToken {
+                blank_space: usize::from(self.config.space_inside_struct_braces),
+                pre_break: Some(','),
+                ..BreakToken::default()
+            });
+        } else {
+            self.word(",");
+            self.space();
+        }
+    }
+
+    // Breaks before the value of a `let`, const or static if the first line of
+    // the value doesn't fit after the `=`, indenting the rest of the box.
+    #[cfg(feature = "full")]
+    pub fn hanging_space(&mut self) {
+        self.scan_break(BreakToken {
+            offset: INDENT,
+            blank_space: 1,
+            hanging: true,
+            ..BreakToken::default()
+        });
+    }
+
     pub fn neverbreak(&mut self) {
         self.scan_break(BreakToken {
             never_break: true,
@@ -1,9 +1,61 @@
 use crate::algorithm::Printer;
 use crate::iter::IterDelimited;
 use crate::INDENT;
-use syn::{Field, Fields, FieldsUnnamed, Variant, VisCrate, VisPublic, VisRestricted, Visibility};
+use syn::punctuated::Punctuated;
+use syn::{
+    Data, DeriveInput, Field, Fields, FieldsNamed, FieldsUnnamed, Token, Variant, VisCrate,
+    VisPublic, VisRestricted, Visibility,
+};
 
 impl Printer {
+    pub fn derive_input(&mut self, input: &DeriveInput) {
+        self.outer_attrs(&input.attrs);
+        self.cbox(INDENT);
+        self.visibility(&input.vis);
+        self.word(match &input.data {
+            Data::Struct(_) => "struct ",
+            Data::Enum(_) => "enum ",
+            Data::Union(_) => "union ",
+        });
+        self.ident(&input.ident);
+        self.generics(&input.generics);
+        match &input.data {
+            Data::Struct(data) => match &data.fields {
+                Fields::Named(fields) => {
+                    self.where_clause_for_body(&input.generics.where_clause);
+                    self.fields_named(fields);
+                }
+                Fields::Unnamed(fields) => {
+                    self.fields_unnamed(fields);
+                    self.where_clause_semi(&input.generics.where_clause);
+                    self.end();
+                }
+                Fields::Unit => {
+                    self.where_clause_semi(&input.generics.where_clause);
+                    self.end();
+                }
+            },
+            Data::Enum(data) => {
+                self.where_clause_for_body(&input.generics.where_clause);
+                self.word("{");
+                self.hardbreak_if_nonempty();
+                for variant in self.variants_in_order(&data.variants) {
+                    self.variant(variant);
+                    self.word(",");
+                    self.hardbreak();
+                }
+                self.offset(-INDENT);
+                self.end();
+                self.word("}");
+            }
+            Data::Union(data) => {
+                self.where_clause_for_body(&input.generics.where_clause);
+                self.fields_named(&data.fields);
+            }
+        }
+        self.hardbreak();
+    }
+
     pub fn variant(&mut self, variant: &Variant) {
         self.outer_attrs(&variant.attrs);
         self.ident(&variant.ident);
@@ -13,7 +65,7 @@ impl Printer {
                 self.word("{");
                 self.cbox(INDENT);
                 self.space();
-                for field in fields.named.iter().delimited() {
+                for field in self.named_fields_in_order(fields).into_iter().delimited() {
                     self.field(&field);
                     self.trailing_comma_or_space(field.is_last);
                 }
@@ -34,6 +86,56 @@ impl Printer {
         }
     }
 
+    pub fn fields_named(&mut self, fields: &FieldsNamed) {
+        if self.config.inline_single_field_structs && fields.named.len() == 1 {
+            self.word("{");
+            self.space();
+            self.field(&fields.named[0]);
+            self.trailing_comma_or_space(true);
+            self.offset(-INDENT);
+            self.end();
+            self.word("}");
+            return;
+        }
+        self.word("{");
+        self.hardbreak_if_nonempty();
+        for field in self.named_fields_in_order(fields) {
+            self.field(field);
+            self.word(",");
+            self.hardbreak();
+        }
+        self.offset(-INDENT);
+        self.end();
+        self.word("}");
+    }
+
+    // Variants in declaration order, or sorted by name if so configured and
+    // no variant has an explicit discriminant. Sorting an enum whose values
+    // are spelled out could change which value each variant gets, since later
+    // variants without one count up from the previous explicit discriminant.
+    pub fn variants_in_order<'a>(
+        &self,
+        variants: &'a Punctuated<Variant, Token![,]>,
+    ) -> Vec<&'a Variant> {
+        let mut ordered: Vec<&Variant> = variants.iter().collect();
+        if self.config.sort_fields_and_variants
+            && variants
+                .iter()
+                .all(|variant| variant.discriminant.is_none())
+        {
+            ordered.sort_by_cached_key(|variant| variant.ident.to_string());
+        }
+        ordered
+    }
+
+    fn named_fields_in_order<'a>(&self, fields: &'a FieldsNamed) -> Vec<&'a Field> {
+        let mut ordered: Vec<&Field> = fields.named.iter().collect();
+        if self.config.sort_fields_and_variants {
+            ordered.sort_by_cached_key(|field| field.ident.as_ref().unwrap().to_string());
+        }
+        ordered
+    }
+
     pub fn fields_unnamed(&mut self, fields: &FieldsUnnamed) {
         self.word("(");
         self.zerobreak();
@@ -46,11 +148,11 @@ impl Printer {
     }
 
     pub fn field(&mut self, field: &Field) {
-        self.outer_attrs(&field.attrs);
+        self.outer_attrs_inline(&field.attrs);
         self.visibility(&field.vis);
         if let Some(ident) = &field.ident {
             self.ident(ident);
-            self.word(": ");
+            self.type_annotation_colon();
         }
         self.ty(&field.ty);
     }
@@ -71,14 +173,23 @@ impl Printer {
 
     fn vis_crate(&mut self, vis: &VisCrate) {
         let _ = vis;
-        self.word("crate ");
+        if self.config.normalize_visibility {
+            self.word("pub(crate) ");
+        } else {
+            self.word("crate ");
+        }
     }
 
     fn vis_restricted(&mut self, vis: &VisRestricted) {
         self.word("pub(");
         // TODO: If we have a path which is not "self" or "super" or "crate",
         // automatically add the "in" token.
-        if vis.in_token.is_some() {
+        let is_keyword = vis.path.leading_colon.is_none()
+            && vis.path.segments.len() == 1
+            && ["crate", "self", "super"]
+                .iter()
+                .any(|keyword| vis.path.segments[0].ident == keyword);
+        if vis.in_token.is_some() && !(self.config.normalize_visibility && is_keyword) {
             self.word("in ");
         }
         self.path(&vis.path);
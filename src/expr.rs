@@ -1,85 +1,124 @@
-use crate::algorithm::{BreakToken, Printer};
+use crate::algorithm::Printer;
 use crate::iter::IterDelimited;
-use crate::stmt;
 use crate::INDENT;
-use proc_macro2::TokenStream;
+use proc_macro2::{Delimiter, Group, TokenStream, TokenTree};
 use syn::punctuated::Punctuated;
 use syn::{
-    token, Arm, Attribute, BinOp, Block, Expr, ExprArray, ExprAssign, ExprAssignOp, ExprAsync,
-    ExprAwait, ExprBinary, ExprBlock, ExprBox, ExprBreak, ExprCall, ExprCast, ExprClosure,
-    ExprContinue, ExprField, ExprForLoop, ExprGroup, ExprIf, ExprIndex, ExprLet, ExprLit, ExprLoop,
-    ExprMacro, ExprMatch, ExprMethodCall, ExprParen, ExprPath, ExprRange, ExprReference,
-    ExprRepeat, ExprReturn, ExprStruct, ExprTry, ExprTryBlock, ExprTuple, ExprType, ExprUnary,
-    ExprUnsafe, ExprWhile, ExprYield, FieldValue, GenericMethodArgument, Index, Label, Member,
-    MethodTurbofish, RangeLimits, ReturnType, Stmt, Token, UnOp,
+    BinOp, Expr, ExprBinary, ExprCall, ExprCast, ExprField, ExprIndex, ExprLit, ExprParen,
+    ExprPath, ExprUnary, Index, Lit, Member, Token, UnOp,
+};
+#[cfg(feature = "full")]
+use {
+    crate::algorithm::BreakToken,
+    crate::attr,
+    crate::stmt,
+    syn::{
+        token, Arm, Attribute, Block, ExprArray, ExprAssign, ExprAssignOp, ExprAsync, ExprAwait,
+        ExprBlock, ExprBox, ExprBreak, ExprClosure, ExprContinue, ExprForLoop, ExprGroup, ExprIf,
+        ExprLet, ExprLoop, ExprMacro, ExprMatch, ExprMethodCall, ExprRange, ExprReference,
+        ExprRepeat, ExprReturn, ExprStruct, ExprTry, ExprTryBlock, ExprTuple, ExprType, ExprUnsafe,
+        ExprWhile, ExprYield, FieldValue, GenericMethodArgument, Label, MethodTurbofish,
+        RangeLimits, ReturnType, Stmt,
+    },
 };
 
 impl Printer {
     pub fn expr(&mut self, expr: &Expr) {
         match expr {
+            #[cfg(feature = "full")]
             Expr::Array(expr) => self.expr_array(expr),
+            #[cfg(feature = "full")]
             Expr::Assign(expr) => self.expr_assign(expr),
+            #[cfg(feature = "full")]
             Expr::AssignOp(expr) => self.expr_assign_op(expr),
+            #[cfg(feature = "full")]
             Expr::Async(expr) => self.expr_async(expr),
+            #[cfg(feature = "full")]
             Expr::Await(expr) => self.expr_await(expr),
             Expr::Binary(expr) => self.expr_binary(expr),
+            #[cfg(feature = "full")]
             Expr::Block(expr) => self.expr_block(expr),
+            #[cfg(feature = "full")]
             Expr::Box(expr) => self.expr_box(expr),
+            #[cfg(feature = "full")]
             Expr::Break(expr) => self.expr_break(expr),
             Expr::Call(expr) => self.expr_call(expr),
             Expr::Cast(expr) => self.expr_cast(expr),
+            #[cfg(feature = "full")]
             Expr::Closure(expr) => self.expr_closure(expr),
+            #[cfg(feature = "full")]
             Expr::Continue(expr) => self.expr_continue(expr),
             Expr::Field(expr) => self.expr_field(expr),
+            #[cfg(feature = "full")]
             Expr::ForLoop(expr) => self.expr_for_loop(expr),
+            #[cfg(feature = "full")]
             Expr::Group(expr) => self.expr_group(expr),
+            #[cfg(feature = "full")]
             Expr::If(expr) => self.expr_if(expr),
             Expr::Index(expr) => self.expr_index(expr),
+            #[cfg(feature = "full")]
             Expr::Let(expr) => self.expr_let(expr),
             Expr::Lit(expr) => self.expr_lit(expr),
+            #[cfg(feature = "full")]
             Expr::Loop(expr) => self.expr_loop(expr),
+            #[cfg(feature = "full")]
             Expr::Macro(expr) => self.expr_macro(expr),
+            #[cfg(feature = "full")]
             Expr::Match(expr) => self.expr_match(expr),
+            #[cfg(feature = "full")]
             Expr::MethodCall(expr) => self.expr_method_call(expr),
             Expr::Paren(expr) => self.expr_paren(expr),
             Expr::Path(expr) => self.expr_path(expr),
+            #[cfg(feature = "full")]
             Expr::Range(expr) => self.expr_range(expr),
+            #[cfg(feature = "full")]
             Expr::Reference(expr) => self.expr_reference(expr),
+            #[cfg(feature = "full")]
             Expr::Repeat(expr) => self.expr_repeat(expr),
+            #[cfg(feature = "full")]
             Expr::Return(expr) => self.expr_return(expr),
+            #[cfg(feature = "full")]
             Expr::Struct(expr) => self.expr_struct(expr),
+            #[cfg(feature = "full")]
             Expr::Try(expr) => self.expr_try(expr),
+            #[cfg(feature = "full")]
             Expr::TryBlock(expr) => self.expr_try_block(expr),
+            #[cfg(feature = "full")]
             Expr::Tuple(expr) => self.expr_tuple(expr),
+            #[cfg(feature = "full")]
             Expr::Type(expr) => self.expr_type(expr),
             Expr::Unary(expr) => self.expr_unary(expr),
+            #[cfg(feature = "full")]
             Expr::Unsafe(expr) => self.expr_unsafe(expr),
             Expr::Verbatim(expr) => self.expr_verbatim(expr),
+            #[cfg(feature = "full")]
             Expr::While(expr) => self.expr_while(expr),
+            #[cfg(feature = "full")]
             Expr::Yield(expr) => self.expr_yield(expr),
-            #[cfg(test)]
-            Expr::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown Expr"),
         }
     }
 
     fn subexpr(&mut self, expr: &Expr) {
         match expr {
+            #[cfg(feature = "full")]
             Expr::Await(expr) => self.subexpr_await(expr),
             Expr::Call(expr) => self.subexpr_call(expr),
             Expr::Field(expr) => self.subexpr_field(expr),
             Expr::Index(expr) => self.subexpr_index(expr),
+            #[cfg(feature = "full")]
             Expr::MethodCall(expr) => self.subexpr_method_call(expr),
+            #[cfg(feature = "full")]
             Expr::Try(expr) => self.subexpr_try(expr),
             _ => {
                 self.cbox(-INDENT);
-                self.expr(expr);
+                self.postfix_operand(expr);
                 self.end();
             }
         }
     }
 
+    #[cfg(feature = "full")]
     // If the given expression is a bare `ExprStruct`, wraps it in parenthesis
     // before appending it to `TokenStream`.
     fn wrap_exterior_struct(&mut self, expr: &Expr) {
@@ -100,6 +139,7 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_array(&mut self, expr: &ExprArray) {
         self.outer_attrs(&expr.attrs);
         self.word("[");
@@ -115,6 +155,7 @@ impl Printer {
         self.word("]");
     }
 
+    #[cfg(feature = "full")]
     fn expr_assign(&mut self, expr: &ExprAssign) {
         self.outer_attrs(&expr.attrs);
         self.ibox(0);
@@ -124,6 +165,7 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_assign_op(&mut self, expr: &ExprAssignOp) {
         self.outer_attrs(&expr.attrs);
         self.ibox(INDENT);
@@ -131,23 +173,37 @@ impl Printer {
         self.expr(&expr.left);
         self.end();
         self.space();
-        self.binary_operator(&expr.op);
+        self.binary_operator(expr.op);
         self.nbsp();
         self.expr(&expr.right);
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_async(&mut self, expr: &ExprAsync) {
         self.outer_attrs(&expr.attrs);
-        self.word("async ");
-        if expr.capture.is_some() {
-            self.word("move ");
-        }
+        self.async_move_qualifiers(false, true, expr.capture.is_some());
         self.cbox(INDENT);
         self.small_block(&expr.block, &expr.attrs);
         self.end();
     }
 
+    // The keywords before an async block or a closure, in the only order the
+    // parser accepts: `static async move`.
+    #[cfg(feature = "full")]
+    fn async_move_qualifiers(&mut self, movability: bool, asyncness: bool, capture: bool) {
+        if movability {
+            self.word("static ");
+        }
+        if asyncness {
+            self.word("async ");
+        }
+        if capture {
+            self.word("move ");
+        }
+    }
+
+    #[cfg(feature = "full")]
     fn expr_await(&mut self, expr: &ExprAwait) {
         self.outer_attrs(&expr.attrs);
         self.cbox(INDENT);
@@ -155,6 +211,7 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn subexpr_await(&mut self, expr: &ExprAwait) {
         self.subexpr(&expr.base);
         self.zerobreak();
@@ -165,15 +222,16 @@ impl Printer {
         self.outer_attrs(&expr.attrs);
         self.ibox(INDENT);
         self.ibox(-INDENT);
-        self.expr(&expr.left);
+        self.left_operand(&expr.left, expr.op);
         self.end();
         self.space();
-        self.binary_operator(&expr.op);
+        self.binary_operator(expr.op);
         self.nbsp();
-        self.expr(&expr.right);
+        self.right_operand(&expr.right, expr.op);
         self.end();
     }
 
+    #[cfg(feature = "full")]
     pub fn expr_block(&mut self, expr: &ExprBlock) {
         self.outer_attrs(&expr.attrs);
         if let Some(label) = &expr.label {
@@ -184,12 +242,14 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_box(&mut self, expr: &ExprBox) {
         self.outer_attrs(&expr.attrs);
         self.word("box ");
-        self.expr(&expr.expr);
+        self.prefix_operand(&expr.expr);
     }
 
+    #[cfg(feature = "full")]
     fn expr_break(&mut self, expr: &ExprBreak) {
         self.outer_attrs(&expr.attrs);
         self.word("break");
@@ -205,7 +265,7 @@ impl Printer {
 
     fn expr_call(&mut self, expr: &ExprCall) {
         self.outer_attrs(&expr.attrs);
-        self.expr(&expr.func);
+        self.postfix_operand(&expr.func);
         self.call_args(&expr.args);
     }
 
@@ -218,7 +278,14 @@ impl Printer {
         self.outer_attrs(&expr.attrs);
         self.ibox(INDENT);
         self.ibox(-INDENT);
+        let needs_paren = binds_looser_than_cast(&expr.expr);
+        if needs_paren {
+            self.word("(");
+        }
         self.expr(&expr.expr);
+        if needs_paren {
+            self.word(")");
+        }
         self.end();
         self.space();
         self.word("as ");
@@ -226,18 +293,15 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_closure(&mut self, expr: &ExprClosure) {
         self.outer_attrs(&expr.attrs);
         self.ibox(0);
-        if expr.asyncness.is_some() {
-            self.word("async ");
-        }
-        if expr.movability.is_some() {
-            self.word("static ");
-        }
-        if expr.capture.is_some() {
-            self.word("move ");
-        }
+        self.async_move_qualifiers(
+            expr.movability.is_some(),
+            expr.asyncness.is_some(),
+            expr.capture.is_some(),
+        );
         self.cbox(INDENT);
         self.word("|");
         for pat in expr.inputs.iter().delimited() {
@@ -264,6 +328,9 @@ impl Printer {
                 }
                 self.word("|");
                 self.end();
+                // Measure the parameters on their own, so that a long return
+                // type wraps its generic arguments instead of the parameters.
+                self.neverbreak();
                 self.word(" -> ");
                 self.ty(ty);
                 self.nbsp();
@@ -274,6 +341,7 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_continue(&mut self, expr: &ExprContinue) {
         self.outer_attrs(&expr.attrs);
         self.word("continue");
@@ -291,12 +359,18 @@ impl Printer {
     }
 
     fn subexpr_field(&mut self, expr: &ExprField) {
-        self.subexpr(&expr.base);
+        let is_index = matches!(expr.member, Member::Unnamed(_));
+        if literal_glues_to_dot(&expr.base, is_index) {
+            self.operand(&expr.base, true);
+        } else {
+            self.subexpr(&expr.base);
+        }
         self.zerobreak();
         self.word(".");
         self.member(&expr.member);
     }
 
+    #[cfg(feature = "full")]
     fn expr_for_loop(&mut self, expr: &ExprForLoop) {
         self.outer_attrs(&expr.attrs);
         self.ibox(0);
@@ -322,11 +396,13 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_group(&mut self, expr: &ExprGroup) {
         self.outer_attrs(&expr.attrs);
         self.expr(&expr.expr);
     }
 
+    #[cfg(feature = "full")]
     fn expr_if(&mut self, expr: &ExprIf) {
         self.outer_attrs(&expr.attrs);
         self.cbox(INDENT);
@@ -368,7 +444,9 @@ impl Printer {
                 break;
             }
         } else if expr.then_branch.stmts.is_empty() {
-            self.word("{}");
+            self.word("{");
+            self.empty_block_interior();
+            self.word("}");
         } else {
             self.word("{");
             self.hardbreak();
@@ -383,19 +461,31 @@ impl Printer {
 
     fn expr_index(&mut self, expr: &ExprIndex) {
         self.outer_attrs(&expr.attrs);
-        self.expr(&expr.expr);
-        self.word("[");
-        self.expr(&expr.index);
-        self.word("]");
+        self.postfix_operand(&expr.expr);
+        self.index_brackets(&expr.index);
     }
 
     fn subexpr_index(&mut self, expr: &ExprIndex) {
         self.subexpr(&expr.expr);
+        self.index_brackets(&expr.index);
+    }
+
+    // An index too long for the rest of the line moves inside the brackets
+    // on a line of its own, like the single argument of a call.
+    fn index_brackets(&mut self, index: &Expr) {
         self.word("[");
-        self.expr(&expr.index);
+        self.cbox(INDENT);
+        self.zerobreak();
+        self.ibox(0);
+        self.expr(index);
+        self.end();
+        self.zerobreak();
+        self.offset(-INDENT);
+        self.end();
         self.word("]");
     }
 
+    #[cfg(feature = "full")]
     fn expr_let(&mut self, expr: &ExprLet) {
         self.outer_attrs(&expr.attrs);
         self.ibox(INDENT);
@@ -405,7 +495,8 @@ impl Printer {
         self.end();
         self.space();
         self.word("= ");
-        let needs_paren = contains_exterior_struct_lit(&expr.expr);
+        let needs_paren =
+            contains_exterior_struct_lit(&expr.expr) || binds_looser_than_let(&expr.expr);
         if needs_paren {
             self.word("(");
         }
@@ -421,6 +512,7 @@ impl Printer {
         self.lit(&expr.lit);
     }
 
+    #[cfg(feature = "full")]
     fn expr_loop(&mut self, expr: &ExprLoop) {
         self.outer_attrs(&expr.attrs);
         if let Some(label) = &expr.label {
@@ -438,11 +530,13 @@ impl Printer {
         self.word("}");
     }
 
+    #[cfg(feature = "full")]
     fn expr_macro(&mut self, expr: &ExprMacro) {
         self.outer_attrs(&expr.attrs);
         self.mac(&expr.mac, None);
     }
 
+    #[cfg(feature = "full")]
     fn expr_match(&mut self, expr: &ExprMatch) {
         self.outer_attrs(&expr.attrs);
         self.ibox(0);
@@ -463,15 +557,26 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_method_call(&mut self, expr: &ExprMethodCall) {
         self.outer_attrs(&expr.attrs);
         self.cbox(INDENT);
         self.subexpr_method_call(expr);
-        self.end();
+        if is_chain_link(&expr.receiver) {
+            let chain_width = self.config.chain_width.unwrap_or(self.config.max_width);
+            self.end_with_max_width(chain_width as isize);
+        } else {
+            self.end();
+        }
     }
 
+    #[cfg(feature = "full")]
     fn subexpr_method_call(&mut self, expr: &ExprMethodCall) {
-        self.subexpr(&expr.receiver);
+        if literal_glues_to_dot(&expr.receiver, false) {
+            self.operand(&expr.receiver, true);
+        } else {
+            self.subexpr(&expr.receiver);
+        }
         self.zerobreak();
         self.word(".");
         self.ident(&expr.method);
@@ -494,10 +599,14 @@ impl Printer {
         self.qpath(&expr.qself, &expr.path);
     }
 
+    #[cfg(feature = "full")]
     fn expr_range(&mut self, expr: &ExprRange) {
         self.outer_attrs(&expr.attrs);
         if let Some(from) = &expr.from {
             self.expr(from);
+            if ends_with_bare_float_dot(from) {
+                self.nbsp();
+            }
         }
         self.word(match expr.limits {
             RangeLimits::HalfOpen(_) => "..",
@@ -508,25 +617,33 @@ impl Printer {
         }
     }
 
+    #[cfg(feature = "full")]
     fn expr_reference(&mut self, expr: &ExprReference) {
         self.outer_attrs(&expr.attrs);
         self.word("&");
         if expr.mutability.is_some() {
             self.word("mut ");
         }
-        self.expr(&expr.expr);
+        self.prefix_operand(&expr.expr);
     }
 
+    #[cfg(feature = "full")]
     fn expr_repeat(&mut self, expr: &ExprRepeat) {
         self.outer_attrs(&expr.attrs);
         self.word("[");
         self.inner_attrs(&expr.attrs);
+        self.ibox(INDENT);
+        self.ibox(-INDENT);
         self.expr(&expr.expr);
-        self.word("; ");
+        self.end();
+        self.word(";");
+        self.space();
         self.expr(&expr.len);
+        self.end();
         self.word("]");
     }
 
+    #[cfg(feature = "full")]
     fn expr_return(&mut self, expr: &ExprReturn) {
         self.outer_attrs(&expr.attrs);
         self.word("return");
@@ -536,38 +653,44 @@ impl Printer {
         }
     }
 
+    #[cfg(feature = "full")]
     fn expr_struct(&mut self, expr: &ExprStruct) {
         self.outer_attrs(&expr.attrs);
         self.cbox(INDENT);
         self.path(&expr.path);
         self.word(" {");
-        self.space_if_nonempty();
+        self.struct_brace_space_if_nonempty();
         self.inner_attrs(&expr.attrs);
         for field_value in expr.fields.iter().delimited() {
             self.field_value(&field_value);
-            self.trailing_comma_or_space(field_value.is_last && expr.rest.is_none());
+            self.struct_trailing_comma_or_space(field_value.is_last && expr.dot2_token.is_none());
         }
-        if let Some(rest) = &expr.rest {
+        if expr.dot2_token.is_some() {
             self.word("..");
-            self.expr(rest);
-            self.space();
+            if let Some(rest) = &expr.rest {
+                self.expr(rest);
+            }
+            self.struct_brace_space();
         }
         self.offset(-INDENT);
-        self.end_with_max_width(34);
+        self.end_with_max_width(self.config.struct_lit_width as isize);
         self.word("}");
     }
 
+    #[cfg(feature = "full")]
     fn expr_try(&mut self, expr: &ExprTry) {
         self.outer_attrs(&expr.attrs);
-        self.expr(&expr.expr);
+        self.postfix_operand(&expr.expr);
         self.word("?");
     }
 
+    #[cfg(feature = "full")]
     fn subexpr_try(&mut self, expr: &ExprTry) {
         self.subexpr(&expr.expr);
         self.word("?");
     }
 
+    #[cfg(feature = "full")]
     fn expr_try_block(&mut self, expr: &ExprTryBlock) {
         self.outer_attrs(&expr.attrs);
         self.word("try ");
@@ -576,6 +699,7 @@ impl Printer {
         self.end();
     }
 
+    #[cfg(feature = "full")]
     fn expr_tuple(&mut self, expr: &ExprTuple) {
         self.outer_attrs(&expr.attrs);
         self.word("(");
@@ -584,13 +708,19 @@ impl Printer {
         self.inner_attrs(&expr.attrs);
         for elem in expr.elems.iter().delimited() {
             self.expr(&elem);
-            self.trailing_comma(elem.is_last);
+            if expr.elems.len() == 1 {
+                self.word(",");
+                self.zerobreak();
+            } else {
+                self.trailing_comma(elem.is_last);
+            }
         }
         self.offset(-INDENT);
         self.end();
         self.word(")");
     }
 
+    #[cfg(feature = "full")]
     fn expr_type(&mut self, expr: &ExprType) {
         self.outer_attrs(&expr.attrs);
         self.ibox(INDENT);
@@ -598,17 +728,18 @@ impl Printer {
         self.expr(&expr.expr);
         self.end();
         self.space();
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&expr.ty);
         self.end();
     }
 
     fn expr_unary(&mut self, expr: &ExprUnary) {
         self.outer_attrs(&expr.attrs);
-        self.unary_operator(&expr.op);
-        self.expr(&expr.expr);
+        self.unary_operator(expr.op);
+        self.prefix_operand(&expr.expr);
     }
 
+    #[cfg(feature = "full")]
     fn expr_unsafe(&mut self, expr: &ExprUnsafe) {
         self.outer_attrs(&expr.attrs);
         self.word("unsafe {");
@@ -631,11 +762,88 @@ impl Printer {
     }
 
     fn expr_verbatim(&mut self, expr: &TokenStream) {
-        if !expr.is_empty() {
-            unimplemented!("Expr::Verbatim `{}`", expr);
+        // Syn represents the inferred expression `_`, as on the left-hand side
+        // of a destructuring assignment, and inline const blocks `const { N }`
+        // as verbatim tokens. It doesn't parse the unstable tail call
+        // `become f(x)` or `do yeet err`, but these can be constructed as
+        // verbatim tokens.
+        let mut tokens = expr.clone().into_iter();
+        match tokens.next() {
+            None => {}
+            Some(TokenTree::Ident(ident)) if ident == "_" && tokens.clone().next().is_none() => {
+                self.word("_");
+            }
+            Some(TokenTree::Ident(ident)) if ident == "become" => {
+                self.word("become ");
+                self.ibox(0);
+                self.macro_rules_tokens(tokens.collect(), false);
+                self.end();
+            }
+            Some(TokenTree::Ident(ident))
+                if ident == "do"
+                    && matches!(tokens.clone().next(), Some(TokenTree::Ident(ident)) if ident == "yeet") =>
+            {
+                self.word("do yeet");
+                let operand: TokenStream = tokens.skip(1).collect();
+                if !operand.is_empty() {
+                    self.nbsp();
+                    self.ibox(0);
+                    self.macro_rules_tokens(operand, false);
+                    self.end();
+                }
+            }
+            Some(TokenTree::Ident(ident)) if ident == "const" => {
+                let group = match (tokens.next(), tokens.next()) {
+                    (Some(TokenTree::Group(group)), None)
+                        if group.delimiter() == Delimiter::Brace =>
+                    {
+                        group
+                    }
+                    _ => return self.verbatim("Expr", expr),
+                };
+                self.word("const ");
+                if self.reparsed_block(&group) {
+                    return;
+                }
+                let body = group.stream();
+                self.word("{");
+                if !body.is_empty() {
+                    self.cbox(INDENT);
+                    self.space();
+                    self.ibox(0);
+                    self.macro_rules_tokens(body, false);
+                    self.end();
+                    self.space();
+                    self.offset(-INDENT);
+                    self.end();
+                }
+                self.word("}");
+            }
+            _ => self.verbatim("Expr", expr),
         }
     }
 
+    // Prints the braced body of an inline const block with the block printer,
+    // if syn can parse it as a block.
+    #[cfg(feature = "parsing")]
+    fn reparsed_block(&mut self, group: &Group) -> bool {
+        let block: Block = match syn::parse2(TokenTree::Group(group.clone()).into()) {
+            Ok(block) => block,
+            Err(_) => return false,
+        };
+        self.cbox(INDENT);
+        self.small_block(&block, &[]);
+        self.end();
+        true
+    }
+
+    #[cfg(not(feature = "parsing"))]
+    fn reparsed_block(&mut self, group: &Group) -> bool {
+        let _ = group;
+        false
+    }
+
+    #[cfg(feature = "full")]
     fn expr_while(&mut self, expr: &ExprWhile) {
         self.outer_attrs(&expr.attrs);
         if let Some(label) = &expr.label {
@@ -656,6 +864,7 @@ impl Printer {
         self.word("}");
     }
 
+    #[cfg(feature = "full")]
     fn expr_yield(&mut self, expr: &ExprYield) {
         self.outer_attrs(&expr.attrs);
         self.word("yield");
@@ -665,11 +874,61 @@ impl Printer {
         }
     }
 
+    // Keyword-prefixed expressions like `yield x` swallow everything to their
+    // right, so as the left operand of an infix operator they need parens. A
+    // `let` would likewise swallow any operator tighter than `&&` into its
+    // scrutinee, a `<` or `<<` right after the type of a cast would be parsed
+    // as the start of generic arguments, and comparisons don't chain.
+    fn left_operand(&mut self, expr: &Expr, op: BinOp) {
+        let precedence = Precedence::of(expr);
+        let op_precedence = Precedence::of_binop(op);
+        let needs_paren = is_open_ended_prefix(expr)
+            || is_let(expr) && !matches!(op, BinOp::And(_) | BinOp::Or(_))
+            || ends_with_cast(expr) && matches!(op, BinOp::Lt(_) | BinOp::Shl(_))
+            || precedence > Precedence::Jump && precedence < op_precedence
+            || precedence == Precedence::Compare && op_precedence == Precedence::Compare;
+        self.operand(expr, needs_paren);
+    }
+
+    // Operands parsed from source only bind looser than their operator if
+    // they are parenthesized, but an invisible group from a macro expansion,
+    // like `$e * 2` with `$e` bound to `1 + 1`, needs parens to be added.
+    // Operators are left-associative, so a right operand at the same level
+    // needs them too: `a - (b - c)`.
+    fn right_operand(&mut self, expr: &Expr, op: BinOp) {
+        let precedence = Precedence::of(expr);
+        let needs_paren = precedence > Precedence::Jump && precedence <= Precedence::of_binop(op);
+        self.operand(expr, needs_paren);
+    }
+
+    fn prefix_operand(&mut self, expr: &Expr) {
+        let precedence = Precedence::of(expr);
+        let needs_paren = precedence > Precedence::Jump && precedence < Precedence::Prefix;
+        self.operand(expr, needs_paren);
+    }
+
+    fn postfix_operand(&mut self, expr: &Expr) {
+        let needs_paren = Precedence::of(expr) < Precedence::Unambiguous;
+        self.operand(expr, needs_paren);
+    }
+
+    fn operand(&mut self, expr: &Expr, needs_paren: bool) {
+        if needs_paren {
+            self.word("(");
+        }
+        self.expr(expr);
+        if needs_paren {
+            self.word(")");
+        }
+    }
+
+    #[cfg(feature = "full")]
     fn label(&mut self, label: &Label) {
         self.lifetime(&label.name);
         self.word(": ");
     }
 
+    #[cfg(feature = "full")]
     fn field_value(&mut self, field_value: &FieldValue) {
         self.outer_attrs(&field_value.attrs);
         self.member(&field_value.member);
@@ -681,10 +940,11 @@ impl Printer {
         }
     }
 
+    #[cfg(feature = "full")]
     fn arm(&mut self, arm: &Arm) {
         self.outer_attrs(&arm.attrs);
         self.ibox(0);
-        self.pat(&arm.pat);
+        self.pat_without_parens(&arm.pat);
         if let Some((_if_token, guard)) = &arm.guard {
             self.word(" if ");
             self.expr(guard);
@@ -732,6 +992,17 @@ impl Printer {
             self.end();
             self.word("}");
             self.end();
+        } else if self.config.match_arm_blocks {
+            self.nbsp();
+            self.word("{");
+            self.cbox(INDENT);
+            self.space();
+            self.expr(body);
+            self.space();
+            self.offset(-INDENT);
+            self.end();
+            self.word("}");
+            self.end();
         } else {
             self.nbsp();
             self.neverbreak();
@@ -753,6 +1024,7 @@ impl Printer {
         }
     }
 
+    #[cfg(feature = "full")]
     fn method_turbofish(&mut self, turbofish: &MethodTurbofish) {
         self.word("::<");
         self.cbox(0);
@@ -766,6 +1038,7 @@ impl Printer {
         self.word(">");
     }
 
+    #[cfg(feature = "full")]
     fn generic_method_argument(&mut self, generic: &GenericMethodArgument) {
         match generic {
             GenericMethodArgument::Type(arg) => self.ty(arg),
@@ -793,21 +1066,26 @@ impl Printer {
         self.word(")");
     }
 
+    #[cfg(feature = "full")]
     fn small_block(&mut self, block: &Block, attrs: &[Attribute]) {
         self.word("{");
-        self.space_if_nonempty();
-        self.inner_attrs(attrs);
-        if let (Some(Stmt::Expr(expr)), None) = (block.stmts.get(0), block.stmts.get(1)) {
-            self.ibox(0);
-            self.expr(expr);
-            self.end();
+        if attr::has_inner(attrs) || !block.stmts.is_empty() {
             self.space();
-        } else {
-            for stmt in &block.stmts {
-                self.stmt(stmt);
+            self.inner_attrs(attrs);
+            if let (Some(Stmt::Expr(expr)), None) = (block.stmts.first(), block.stmts.get(1)) {
+                self.ibox(0);
+                self.expr(expr);
+                self.end();
+                self.space();
+            } else {
+                for stmt in &block.stmts {
+                    self.stmt(stmt);
+                }
             }
+            self.offset(-INDENT);
+        } else {
+            self.empty_block_interior();
         }
-        self.offset(-INDENT);
         self.word("}");
     }
 
@@ -822,7 +1100,7 @@ impl Printer {
         self.word(member.index.to_string());
     }
 
-    fn binary_operator(&mut self, op: &BinOp) {
+    fn binary_operator(&mut self, op: BinOp) {
         self.word(match op {
             BinOp::Add(_) => "+",
             BinOp::Sub(_) => "-",
@@ -855,7 +1133,7 @@ impl Printer {
         });
     }
 
-    fn unary_operator(&mut self, op: &UnOp) {
+    fn unary_operator(&mut self, op: UnOp) {
         self.word(match op {
             UnOp::Deref(_) => "*",
             UnOp::Not(_) => "!",
@@ -864,6 +1142,7 @@ impl Printer {
     }
 }
 
+#[cfg(feature = "full")]
 pub fn requires_terminator(expr: &Expr) -> bool {
     // see https://github.com/rust-lang/rust/blob/2679c38fc/src/librustc_ast/util/classify.rs#L7-L25
     match expr {
@@ -880,6 +1159,7 @@ pub fn requires_terminator(expr: &Expr) -> bool {
     }
 }
 
+#[cfg(feature = "full")]
 // Expressions that syntactically contain an "exterior" struct literal i.e. not
 // surrounded by any parens or other delimiters. For example `X { y: 1 }`, `X {
 // y: 1 }.method()`, `foo == X { y: 1 }` and `X { y: 1 } == foo` all do, but `(X
@@ -912,6 +1192,178 @@ fn contains_exterior_struct_lit(expr: &Expr) -> bool {
     }
 }
 
+// How tightly an expression binds, from the keyword-prefixed expressions like
+// `return x` that swallow everything to their right, up to those that never
+// need parens, like paths, calls and parenthesized expressions.
+#[derive(PartialEq, PartialOrd)]
+enum Precedence {
+    Jump,
+    Assign,
+    Range,
+    Or,
+    And,
+    Compare,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
+    Arithmetic,
+    Term,
+    Cast,
+    Prefix,
+    Unambiguous,
+}
+
+impl Precedence {
+    fn of(expr: &Expr) -> Self {
+        match expr {
+            Expr::Break(_) | Expr::Closure(_) | Expr::Let(_) | Expr::Return(_) | Expr::Yield(_) => {
+                Precedence::Jump
+            }
+            Expr::Assign(_) | Expr::AssignOp(_) => Precedence::Assign,
+            Expr::Range(_) => Precedence::Range,
+            Expr::Binary(expr) => Precedence::of_binop(expr.op),
+            Expr::Cast(_) | Expr::Type(_) => Precedence::Cast,
+            Expr::Box(_) | Expr::Reference(_) | Expr::Unary(_) => Precedence::Prefix,
+            #[cfg(feature = "full")]
+            Expr::Group(group) => Precedence::of(&group.expr),
+            _ => Precedence::Unambiguous,
+        }
+    }
+
+    fn of_binop(op: BinOp) -> Self {
+        match op {
+            BinOp::Mul(_) | BinOp::Div(_) | BinOp::Rem(_) => Precedence::Term,
+            BinOp::Add(_) | BinOp::Sub(_) => Precedence::Arithmetic,
+            BinOp::Shl(_) | BinOp::Shr(_) => Precedence::Shift,
+            BinOp::BitAnd(_) => Precedence::BitAnd,
+            BinOp::BitXor(_) => Precedence::BitXor,
+            BinOp::BitOr(_) => Precedence::BitOr,
+            BinOp::Eq(_)
+            | BinOp::Lt(_)
+            | BinOp::Le(_)
+            | BinOp::Ne(_)
+            | BinOp::Ge(_)
+            | BinOp::Gt(_) => Precedence::Compare,
+            BinOp::And(_) => Precedence::And,
+            BinOp::Or(_) => Precedence::Or,
+            BinOp::AddEq(_)
+            | BinOp::SubEq(_)
+            | BinOp::MulEq(_)
+            | BinOp::DivEq(_)
+            | BinOp::RemEq(_)
+            | BinOp::BitXorEq(_)
+            | BinOp::BitAndEq(_)
+            | BinOp::BitOrEq(_)
+            | BinOp::ShlEq(_)
+            | BinOp::ShrEq(_) => Precedence::Assign,
+        }
+    }
+}
+
+fn is_open_ended_prefix(expr: &Expr) -> bool {
+    match expr {
+        Expr::Break(_) | Expr::Closure(_) | Expr::Return(_) | Expr::Yield(_) => true,
+        #[cfg(feature = "full")]
+        Expr::Group(group) => is_open_ended_prefix(&group.expr),
+        _ => false,
+    }
+}
+
+fn is_let(expr: &Expr) -> bool {
+    match expr {
+        Expr::Let(_) => true,
+        #[cfg(feature = "full")]
+        Expr::Group(group) => is_let(&group.expr),
+        _ => false,
+    }
+}
+
+// Whether a method call on this receiver continues a chain of at least two
+// links, as in `a.b().c()` or `a.b?.c()`.
+#[cfg(feature = "full")]
+fn is_chain_link(receiver: &Expr) -> bool {
+    match receiver {
+        Expr::Await(_) | Expr::Field(_) | Expr::MethodCall(_) => true,
+        Expr::Try(expr) => is_chain_link(&expr.expr),
+        _ => false,
+    }
+}
+
+// Operands that need parens to stay intact as the expression being cast,
+// because `as` binds tighter than them: `(a + b) as f64`.
+fn binds_looser_than_cast(expr: &Expr) -> bool {
+    match expr {
+        Expr::Assign(_) | Expr::AssignOp(_) | Expr::Binary(_) | Expr::Let(_) | Expr::Range(_) => {
+            true
+        }
+        #[cfg(feature = "full")]
+        Expr::Group(group) => binds_looser_than_cast(&group.expr),
+        _ => is_open_ended_prefix(expr),
+    }
+}
+
+fn ends_with_cast(expr: &Expr) -> bool {
+    match expr {
+        Expr::Cast(_) => true,
+        Expr::Binary(expr) => ends_with_cast(&expr.right),
+        #[cfg(feature = "full")]
+        Expr::Group(group) => ends_with_cast(&group.expr),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "full")]
+// Expressions that would not stay intact as the scrutinee of `let PAT = EXPR`,
+// because the `let` binds tighter than them: `let Some(x) = (a || b)`.
+fn binds_looser_than_let(expr: &Expr) -> bool {
+    match expr {
+        Expr::Binary(ExprBinary {
+            op: BinOp::And(_) | BinOp::Or(_),
+            ..
+        })
+        | Expr::Assign(_)
+        | Expr::AssignOp(_)
+        | Expr::Range(_) => true,
+        Expr::Group(group) => binds_looser_than_let(&group.expr),
+        _ => is_open_ended_prefix(expr),
+    }
+}
+
+// A literal that would lex together with a `.` directly after it: `1.0` is a
+// float rather than field `0` of `1`, and `1..0` and `1..f()` are ranges.
+fn literal_glues_to_dot(expr: &Expr, followed_by_index: bool) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Int(lit), ..
+        }) => followed_by_index && lit.suffix().is_empty(),
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.to_string().ends_with('.'),
+        _ => false,
+    }
+}
+
+// A float literal like `1.` directly followed by `..` would lex as `1` and
+// `...`, so a range starting with one needs a space before the operator.
+#[cfg(feature = "full")]
+pub fn ends_with_bare_float_dot(expr: &Expr) -> bool {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Float(lit),
+            ..
+        }) => lit.to_string().ends_with('.'),
+        Expr::Binary(ExprBinary { right: e, .. })
+        | Expr::Box(ExprBox { expr: e, .. })
+        | Expr::Group(ExprGroup { expr: e, .. })
+        | Expr::Reference(ExprReference { expr: e, .. })
+        | Expr::Unary(ExprUnary { expr: e, .. }) => ends_with_bare_float_dot(e),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "full")]
 fn needs_newline_if_wrap(expr: &Expr) -> bool {
     match expr {
         Expr::Array(_)
@@ -961,9 +1413,6 @@ fn needs_newline_if_wrap(expr: &Expr) -> bool {
         | Expr::Unary(ExprUnary { expr: e, .. })
         | Expr::Yield(ExprYield { expr: Some(e), .. }) => needs_newline_if_wrap(e),
 
-        #[cfg(test)]
-        Expr::__TestExhaustive(_) => unreachable!(),
-        #[cfg(not(test))]
         _ => false,
     }
 }
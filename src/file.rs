@@ -1,17 +1,64 @@
 use crate::algorithm::Printer;
+use crate::color::Category;
+use crate::item;
+use crate::ItemOrder;
+use std::ops::Range;
 use syn::File;
 
 impl Printer {
     pub fn file(&mut self, file: &File) {
         self.cbox(0);
+        self.file_header(file);
+        let canonical = self.config.item_order == ItemOrder::Canonical;
+        for (i, item) in item::items_in_order(&file.items, canonical)
+            .into_iter()
+            .enumerate()
+        {
+            if i > 0 {
+                for _ in 0..self.config.blank_lines_between_items {
+                    self.hardbreak();
+                }
+            }
+            self.item(item);
+        }
+        self.end();
+    }
+
+    // Prints the file one item at a time, flushing after each to find the
+    // range of the output it took up, not counting its trailing newline.
+    // Top-level items always start on a fresh line at the left margin, so
+    // this produces the same text as `file`.
+    pub fn file_with_spans(&mut self, file: &File) -> (String, Vec<Range<usize>>) {
+        self.cbox(0);
+        self.file_header(file);
+        // Break the header's box, as the box around the whole file would be,
+        // so that consecutive inner attributes go on separate lines.
+        self.end_with_max_width(0);
+        let mut out = self.take_output();
+        let mut spans = Vec::with_capacity(file.items.len());
+        for (i, item) in file.items.iter().enumerate() {
+            self.cbox(0);
+            if i > 0 {
+                for _ in 0..self.config.blank_lines_between_items {
+                    self.hardbreak();
+                }
+            }
+            self.item(item);
+            self.end();
+            let text = self.take_output();
+            let start = out.len() + text.len() - text.trim_start_matches('\n').len();
+            let end = out.len() + text.trim_end_matches('\n').len();
+            spans.push(start..end);
+            out.push_str(&text);
+        }
+        (out, spans)
+    }
+
+    fn file_header(&mut self, file: &File) {
         if let Some(shebang) = &file.shebang {
-            self.word(shebang.clone());
+            self.highlighted(Category::Comment, shebang.clone());
             self.hardbreak();
         }
         self.inner_attrs(&file.attrs);
-        for item in &file.items {
-            self.item(item);
-        }
-        self.end();
     }
 }
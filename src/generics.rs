@@ -1,10 +1,12 @@
 use crate::algorithm::Printer;
 use crate::iter::IterDelimited;
-use crate::INDENT;
+use crate::token::Token;
+use crate::{Config, INDENT};
+use proc_macro2::{Spacing, TokenStream};
 use syn::{
     BoundLifetimes, ConstParam, GenericParam, Generics, LifetimeDef, PredicateEq,
-    PredicateLifetime, PredicateType, TraitBound, TraitBoundModifier, TypeParam, TypeParamBound,
-    WhereClause, WherePredicate,
+    PredicateLifetime, PredicateType, TraitBound, TraitBoundModifier, Type, TypeParam,
+    TypeParamBound, WhereClause, WherePredicate,
 };
 
 impl Printer {
@@ -79,6 +81,14 @@ impl Printer {
         self.outer_attrs(&type_param.attrs);
         self.ident(&type_param.ident);
         self.ibox(INDENT);
+        // Syn keeps all the bounds of a type parameter as verbatim tokens in
+        // place of its default when one of them is `~const Trait`.
+        if let (Some(Type::Verbatim(bounds)), None) = (&type_param.default, &type_param.eq_token) {
+            self.word(": ");
+            self.type_param_bounds_verbatim(bounds.clone());
+            self.end();
+            return;
+        }
         for type_param_bound in type_param.bounds.iter().delimited() {
             if type_param_bound.is_first {
                 self.word(": ");
@@ -96,6 +106,38 @@ impl Printer {
         self.end();
     }
 
+    fn type_param_bounds_verbatim(&mut self, tokens: TokenStream) {
+        let mut previous_is_word = false;
+        let mut tokens = tokens.into_iter().map(Token::from).peekable();
+        while let Some(token) = tokens.next() {
+            let is_word = match &token {
+                Token::Ident(_) | Token::Literal(_) => true,
+                Token::Punct('>', _) => true,
+                _ => false,
+            };
+            match token {
+                Token::Ident(_) | Token::Literal(_) if previous_is_word => {
+                    self.nbsp();
+                    self.single_token(token, Self::type_param_bounds_verbatim);
+                }
+                Token::Punct('+', _) => {
+                    self.space();
+                    self.word("+ ");
+                }
+                Token::Punct(',', _) => self.word(", "),
+                Token::Punct('=', _) => self.word(" = "),
+                Token::Punct('-', Spacing::Joint)
+                    if matches!(tokens.peek(), Some(Token::Punct('>', _))) =>
+                {
+                    tokens.next();
+                    self.word(" -> ");
+                }
+                token => self.single_token(token, Self::type_param_bounds_verbatim),
+            }
+            previous_is_word = is_word;
+        }
+    }
+
     pub fn type_param_bound(&mut self, type_param_bound: &TypeParamBound) {
         match type_param_bound {
             TypeParamBound::Trait(trait_bound) => self.trait_bound(trait_bound),
@@ -114,7 +156,7 @@ impl Printer {
             }
             _ => 0,
         };
-        self.trait_bound_modifier(&trait_bound.modifier);
+        self.trait_bound_modifier(trait_bound.modifier);
         if let Some(bound_lifetimes) = &trait_bound.lifetimes {
             self.bound_lifetimes(bound_lifetimes);
         }
@@ -129,7 +171,7 @@ impl Printer {
         }
     }
 
-    fn trait_bound_modifier(&mut self, trait_bound_modifier: &TraitBoundModifier) {
+    fn trait_bound_modifier(&mut self, trait_bound_modifier: TraitBoundModifier) {
         match trait_bound_modifier {
             TraitBoundModifier::None => {}
             TraitBoundModifier::Maybe(_question_mark) => self.word("?"),
@@ -140,7 +182,7 @@ impl Printer {
         self.outer_attrs(&const_param.attrs);
         self.word("const ");
         self.ident(&const_param.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&const_param.ty);
         if let Some(default) = &const_param.default {
             self.word(" = ");
@@ -160,12 +202,14 @@ impl Printer {
         self.where_clause_impl(where_clause, hardbreaks, semi);
     }
 
+    #[cfg(feature = "full")]
     pub fn where_clause_oneline(&mut self, where_clause: &Option<WhereClause>) {
         let hardbreaks = false;
         let semi = false;
         self.where_clause_impl(where_clause, hardbreaks, semi);
     }
 
+    #[cfg(feature = "full")]
     pub fn where_clause_oneline_semi(&mut self, where_clause: &Option<WhereClause>) {
         let hardbreaks = false;
         let semi = true;
@@ -183,18 +227,25 @@ impl Printer {
             _ => {
                 if semi {
                     self.word(";");
+                } else if hardbreaks {
+                    self.space_before_body_brace();
                 } else {
                     self.nbsp();
                 }
                 return;
             }
         };
+        let predicates = if self.config.reorder_where_predicates {
+            canonical_where_predicate_order(where_clause)
+        } else {
+            where_clause.predicates.iter().collect()
+        };
         if hardbreaks {
             self.hardbreak();
             self.offset(-INDENT);
             self.word("where");
             self.hardbreak();
-            for predicate in where_clause.predicates.iter().delimited() {
+            for predicate in predicates.iter().delimited() {
                 self.where_predicate(&predicate);
                 if predicate.is_last && semi {
                     self.word(";");
@@ -211,7 +262,7 @@ impl Printer {
             self.offset(-INDENT);
             self.word("where");
             self.space();
-            for predicate in where_clause.predicates.iter().delimited() {
+            for predicate in predicates.iter().delimited() {
                 self.where_predicate(&predicate);
                 if predicate.is_last && semi {
                     self.word(";");
@@ -274,3 +325,22 @@ impl Printer {
         self.ty(&predicate.rhs_ty);
     }
 }
+
+// Lifetime predicates, then type predicates sorted by the bounded type, then
+// equality predicates sorted by their left-hand side. Predicates that compare
+// equal keep their relative order.
+fn canonical_where_predicate_order(where_clause: &WhereClause) -> Vec<&WherePredicate> {
+    fn type_text(ty: &Type) -> String {
+        let mut printer = Printer::new(&Config::default());
+        printer.ty(ty);
+        printer.eof()
+    }
+
+    let mut ordered: Vec<&WherePredicate> = where_clause.predicates.iter().collect();
+    ordered.sort_by_cached_key(|predicate| match predicate {
+        WherePredicate::Lifetime(predicate) => (0, predicate.lifetime.ident.to_string()),
+        WherePredicate::Type(predicate) => (1, type_text(&predicate.bounded_ty)),
+        WherePredicate::Eq(predicate) => (2, type_text(&predicate.lhs_ty)),
+    });
+    ordered
+}
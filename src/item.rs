@@ -1,14 +1,18 @@
 use crate::algorithm::Printer;
+use crate::attr;
 use crate::iter::IterDelimited;
 use crate::INDENT;
-use proc_macro2::TokenStream;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
+use std::borrow::Cow;
+use syn::visit::{self, Visit};
 use syn::{
-    Fields, FnArg, ForeignItem, ForeignItemFn, ForeignItemMacro, ForeignItemStatic,
-    ForeignItemType, ImplItem, ImplItemConst, ImplItemMacro, ImplItemMethod, ImplItemType, Item,
-    ItemConst, ItemEnum, ItemExternCrate, ItemFn, ItemForeignMod, ItemImpl, ItemMacro, ItemMacro2,
-    ItemMod, ItemStatic, ItemStruct, ItemTrait, ItemTraitAlias, ItemType, ItemUnion, ItemUse, Pat,
-    Receiver, Signature, Stmt, TraitItem, TraitItemConst, TraitItemMacro, TraitItemMethod,
-    TraitItemType, Type, UseGlob, UseGroup, UseName, UsePath, UseRename, UseTree,
+    Block, Expr, ExprLit, Fields, FnArg, ForeignItem, ForeignItemFn, ForeignItemMacro,
+    ForeignItemStatic, ForeignItemType, GenericParam, Ident, ImplItem, ImplItemConst,
+    ImplItemMacro, ImplItemMethod, ImplItemType, Item, ItemConst, ItemEnum, ItemExternCrate,
+    ItemFn, ItemForeignMod, ItemImpl, ItemMacro, ItemMacro2, ItemMod, ItemStatic, ItemStruct,
+    ItemTrait, ItemTraitAlias, ItemType, ItemUnion, ItemUse, Lifetime, Lit, Macro, Pat, Receiver,
+    Signature, Stmt, TraitItem, TraitItemConst, TraitItemMacro, TraitItemMethod, TraitItemType,
+    Type, UseGlob, UseGroup, UseName, UsePath, UseRename, UseTree,
 };
 
 impl Printer {
@@ -31,9 +35,6 @@ impl Printer {
             Item::Union(item) => self.item_union(item),
             Item::Use(item) => self.item_use(item),
             Item::Verbatim(item) => self.item_verbatim(item),
-            #[cfg(test)]
-            Item::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown Item"),
         }
     }
@@ -44,11 +45,9 @@ impl Printer {
         self.visibility(&item.vis);
         self.word("const ");
         self.ident(&item.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&item.ty);
-        self.word(" = ");
-        self.neverbreak();
-        self.expr(&item.expr);
+        self.initializer(&item.expr);
         self.word(";");
         self.end();
         self.hardbreak();
@@ -65,7 +64,7 @@ impl Printer {
         self.word("{");
         self.hardbreak_if_nonempty();
         self.inner_attrs(&item.attrs);
-        for variant in &item.variants {
+        for variant in self.variants_in_order(&item.variants) {
             self.variant(variant);
             self.word(",");
             self.hardbreak();
@@ -90,10 +89,10 @@ impl Printer {
     }
 
     fn item_fn(&mut self, item: &ItemFn) {
-        self.outer_attrs(&item.attrs);
+        self.outer_attrs_inline(&item.attrs);
         self.cbox(INDENT);
         self.visibility(&item.vis);
-        self.signature(&item.sig);
+        self.signature(&item.sig, Some(&item.block));
         self.where_clause_for_body(&item.sig.generics.where_clause);
         self.word("{");
         self.hardbreak_if_nonempty();
@@ -152,8 +151,14 @@ impl Printer {
         self.word("{");
         self.hardbreak_if_nonempty();
         self.inner_attrs(&item.attrs);
-        for impl_item in &item.items {
-            self.impl_item(impl_item);
+        if self.config.reorder_impl_items {
+            for impl_item in canonical_impl_item_order(&item.items) {
+                self.impl_item(impl_item);
+            }
+        } else {
+            for impl_item in &item.items {
+                self.impl_item(impl_item);
+            }
         }
         self.offset(-INDENT);
         self.end();
@@ -168,8 +173,27 @@ impl Printer {
         self.hardbreak();
     }
 
+    pub fn stmt_macro(&mut self, item: &ItemMacro) {
+        self.outer_attrs(&item.attrs);
+        self.mac(&item.mac, item.ident.as_ref());
+        // A semicolon after a brace-delimited macro is optional except at the
+        // end of a block, where it makes the macro a statement rather than
+        // the block's value, so keep it wherever it was written.
+        if item.semi_token.is_some() {
+            self.word(";");
+        } else {
+            self.mac_semi_if_needed(&item.mac.delimiter);
+        }
+        self.hardbreak();
+    }
+
     fn item_macro2(&mut self, item: &ItemMacro2) {
-        unimplemented!("Item::Macro2 `macro {} {}`", item.ident, item.rules);
+        self.outer_attrs(&item.attrs);
+        self.visibility(&item.vis);
+        self.word("macro ");
+        self.ident(&item.ident);
+        self.macro2_rules(&item.rules);
+        self.hardbreak();
     }
 
     fn item_mod(&mut self, item: &ItemMod) {
@@ -179,10 +203,11 @@ impl Printer {
         self.word("mod ");
         self.ident(&item.ident);
         if let Some((_brace, items)) = &item.content {
-            self.word(" {");
+            self.space_before_body_brace();
+            self.word("{");
             self.hardbreak_if_nonempty();
             self.inner_attrs(&item.attrs);
-            for item in items {
+            for item in items_in_order(items, self.config.reorder_modules) {
                 self.item(item);
             }
             self.offset(-INDENT);
@@ -204,11 +229,9 @@ impl Printer {
             self.word("mut ");
         }
         self.ident(&item.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&item.ty);
-        self.word(" = ");
-        self.neverbreak();
-        self.expr(&item.expr);
+        self.initializer(&item.expr);
         self.word(";");
         self.end();
         self.hardbreak();
@@ -224,16 +247,7 @@ impl Printer {
         match &item.fields {
             Fields::Named(fields) => {
                 self.where_clause_for_body(&item.generics.where_clause);
-                self.word("{");
-                self.hardbreak_if_nonempty();
-                for field in &fields.named {
-                    self.field(field);
-                    self.word(",");
-                    self.hardbreak();
-                }
-                self.offset(-INDENT);
-                self.end();
-                self.word("}");
+                self.fields_named(fields);
             }
             Fields::Unnamed(fields) => {
                 self.fields_unnamed(fields);
@@ -291,6 +305,7 @@ impl Printer {
         self.generics(&item.generics);
         self.word(" = ");
         self.neverbreak();
+        self.cbox(0);
         for bound in item.bounds.iter().delimited() {
             if !bound.is_first {
                 self.space();
@@ -298,6 +313,7 @@ impl Printer {
             }
             self.type_param_bound(&bound);
         }
+        self.end();
         self.where_clause_semi(&item.generics.where_clause);
         self.end();
         self.hardbreak();
@@ -313,7 +329,9 @@ impl Printer {
         self.where_clause_oneline(&item.generics.where_clause);
         self.word("= ");
         self.neverbreak();
+        self.ibox(-INDENT);
         self.ty(&item.ty);
+        self.end();
         self.word(";");
         self.end();
         self.hardbreak();
@@ -327,16 +345,7 @@ impl Printer {
         self.ident(&item.ident);
         self.generics(&item.generics);
         self.where_clause_for_body(&item.generics.where_clause);
-        self.word("{");
-        self.hardbreak_if_nonempty();
-        for field in &item.fields.named {
-            self.field(field);
-            self.word(",");
-            self.hardbreak();
-        }
-        self.offset(-INDENT);
-        self.end();
-        self.word("}");
+        self.fields_named(&item.fields);
         self.hardbreak();
     }
 
@@ -353,7 +362,14 @@ impl Printer {
     }
 
     fn item_verbatim(&mut self, item: &TokenStream) {
-        unimplemented!("Item::Verbatim `{}`", item);
+        // Verbatim tokens are sometimes a perfectly ordinary item, for example
+        // one assembled by a code generator, so format it in full if syn can
+        // parse it. Otherwise fall back to spacing out the tokens.
+        if let Some(item) = reparse_item(item) {
+            return self.item(&item);
+        }
+        self.verbatim("Item", item);
+        self.hardbreak();
     }
 
     fn use_tree(&mut self, use_tree: &UseTree) {
@@ -425,18 +441,15 @@ impl Printer {
             ForeignItem::Type(item) => self.foreign_item_type(item),
             ForeignItem::Macro(item) => self.foreign_item_macro(item),
             ForeignItem::Verbatim(item) => self.foreign_item_verbatim(item),
-            #[cfg(test)]
-            ForeignItem::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown ForeignItem"),
         }
     }
 
     fn foreign_item_fn(&mut self, foreign_item: &ForeignItemFn) {
-        self.outer_attrs(&foreign_item.attrs);
+        self.outer_attrs_inline(&foreign_item.attrs);
         self.cbox(INDENT);
         self.visibility(&foreign_item.vis);
-        self.signature(&foreign_item.sig);
+        self.signature(&foreign_item.sig, None);
         self.where_clause_semi(&foreign_item.sig.generics.where_clause);
         self.end();
         self.hardbreak();
@@ -451,7 +464,7 @@ impl Printer {
             self.word("mut ");
         }
         self.ident(&foreign_item.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&foreign_item.ty);
         self.word(";");
         self.end();
@@ -477,7 +490,8 @@ impl Printer {
     }
 
     fn foreign_item_verbatim(&mut self, foreign_item: &TokenStream) {
-        unimplemented!("ForeignItem::Verbatim `{}`", foreign_item);
+        self.verbatim("ForeignItem", foreign_item);
+        self.hardbreak();
     }
 
     fn trait_item(&mut self, trait_item: &TraitItem) {
@@ -487,9 +501,6 @@ impl Printer {
             TraitItem::Type(item) => self.trait_item_type(item),
             TraitItem::Macro(item) => self.trait_item_macro(item),
             TraitItem::Verbatim(item) => self.trait_item_verbatim(item),
-            #[cfg(test)]
-            TraitItem::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown TraitItem"),
         }
     }
@@ -499,12 +510,10 @@ impl Printer {
         self.cbox(0);
         self.word("const ");
         self.ident(&trait_item.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&trait_item.ty);
         if let Some((_eq_token, default)) = &trait_item.default {
-            self.word(" = ");
-            self.neverbreak();
-            self.expr(default);
+            self.initializer(default);
         }
         self.word(";");
         self.end();
@@ -512,9 +521,9 @@ impl Printer {
     }
 
     fn trait_item_method(&mut self, trait_item: &TraitItemMethod) {
-        self.outer_attrs(&trait_item.attrs);
+        self.outer_attrs_inline(&trait_item.attrs);
         self.cbox(INDENT);
-        self.signature(&trait_item.sig);
+        self.signature(&trait_item.sig, trait_item.default.as_ref());
         if let Some(block) = &trait_item.default {
             self.where_clause_for_body(&trait_item.sig.generics.where_clause);
             self.word("{");
@@ -552,7 +561,10 @@ impl Printer {
             self.where_clause_oneline(&trait_item.generics.where_clause);
             self.word("= ");
             self.neverbreak();
+            self.ibox(-INDENT);
             self.ty(default);
+            self.end();
+            self.word(";");
         } else {
             self.where_clause_oneline_semi(&trait_item.generics.where_clause);
         }
@@ -568,7 +580,8 @@ impl Printer {
     }
 
     fn trait_item_verbatim(&mut self, trait_item: &TokenStream) {
-        unimplemented!("TraitItem::Verbatim `{}`", trait_item);
+        self.verbatim("TraitItem", trait_item);
+        self.hardbreak();
     }
 
     fn impl_item(&mut self, impl_item: &ImplItem) {
@@ -578,9 +591,6 @@ impl Printer {
             ImplItem::Type(item) => self.impl_item_type(item),
             ImplItem::Macro(item) => self.impl_item_macro(item),
             ImplItem::Verbatim(item) => self.impl_item_verbatim(item),
-            #[cfg(test)]
-            ImplItem::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown ImplItem"),
         }
     }
@@ -594,24 +604,22 @@ impl Printer {
         }
         self.word("const ");
         self.ident(&impl_item.ident);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&impl_item.ty);
-        self.word(" = ");
-        self.neverbreak();
-        self.expr(&impl_item.expr);
+        self.initializer(&impl_item.expr);
         self.word(";");
         self.end();
         self.hardbreak();
     }
 
     fn impl_item_method(&mut self, impl_item: &ImplItemMethod) {
-        self.outer_attrs(&impl_item.attrs);
+        self.outer_attrs_inline(&impl_item.attrs);
         self.cbox(INDENT);
         self.visibility(&impl_item.vis);
         if impl_item.defaultness.is_some() {
             self.word("default ");
         }
-        self.signature(&impl_item.sig);
+        self.signature(&impl_item.sig, Some(&impl_item.block));
         if impl_item.block.stmts.len() == 1 {
             if let Stmt::Item(Item::Verbatim(verbatim)) = &impl_item.block.stmts[0] {
                 if verbatim.to_string() == ";" {
@@ -648,7 +656,9 @@ impl Printer {
         self.where_clause_oneline(&impl_item.generics.where_clause);
         self.word("= ");
         self.neverbreak();
+        self.ibox(-INDENT);
         self.ty(&impl_item.ty);
+        self.end();
         self.word(";");
         self.end();
         self.hardbreak();
@@ -662,7 +672,8 @@ impl Printer {
     }
 
     fn impl_item_verbatim(&mut self, impl_item: &TokenStream) {
-        unimplemented!("ImplItem::Verbatim `{}`", impl_item);
+        self.verbatim("ImplItem", impl_item);
+        self.hardbreak();
     }
 
     fn maybe_variadic(&mut self, arg: &FnArg) -> bool {
@@ -692,7 +703,27 @@ impl Printer {
         }
     }
 
-    fn signature(&mut self, signature: &Signature) {
+    // The `= value` of a `let`, const or static. The type before it wraps on
+    // its own. The value stays after the `=` if the whole of it fits there, or
+    // if at least its first line does, like the `callee(` of a call that then
+    // breaks inside its parentheses. Otherwise it moves to the next line. A
+    // string literal spanning several lines always stays where it starts.
+    pub fn initializer(&mut self, expr: &Expr) {
+        self.word(" =");
+        self.neverbreak();
+        self.ibox(0);
+        match expr {
+            Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }) if lit.token().to_string().contains('\n') => self.nbsp(),
+            _ => self.hanging_space(),
+        }
+        self.expr(expr);
+        self.end();
+    }
+
+    fn signature(&mut self, signature: &Signature, body: Option<&Block>) {
+        let signature = &*self.elide_single_use_lifetimes(signature, body);
         if signature.constness.is_some() {
             self.word("const ");
         }
@@ -731,6 +762,62 @@ impl Printer {
         self.return_type(&signature.output);
     }
 
+    // A lifetime parameter of a function whose only use is as the lifetime of
+    // a reference argument, as in `fn f<'a>(x: &'a T)`, is dropped and the
+    // reference printed as `&'_ T`. Uses are counted in the signature,
+    // where clause and body, so a lifetime named anywhere else, even inside a
+    // macro call, is left alone.
+    fn elide_single_use_lifetimes<'a>(
+        &self,
+        signature: &'a Signature,
+        body: Option<&Block>,
+    ) -> Cow<'a, Signature> {
+        if !self.config.reformat_lifetimes {
+            return Cow::Borrowed(signature);
+        }
+        let elided: Vec<&Lifetime> = signature
+            .generics
+            .lifetimes()
+            .filter(|param| {
+                param.attrs.is_empty()
+                    && param.bounds.is_empty()
+                    && lifetime_uses(signature, body, &param.lifetime) == 2
+                    && reference_args(signature)
+                        .filter(|lifetime| **lifetime == param.lifetime)
+                        .count()
+                        == 1
+            })
+            .map(|param| &param.lifetime)
+            .collect();
+        if elided.is_empty() {
+            return Cow::Borrowed(signature);
+        }
+
+        let mut elided_signature = signature.clone();
+        elided_signature.generics.params = signature
+            .generics
+            .params
+            .iter()
+            .filter(|param| match param {
+                GenericParam::Lifetime(param) => !elided.contains(&&param.lifetime),
+                _ => true,
+            })
+            .cloned()
+            .collect();
+        for input in &mut elided_signature.inputs {
+            if let FnArg::Typed(arg) = input {
+                if let Type::Reference(ty) = &mut *arg.ty {
+                    if let Some(lifetime) = &mut ty.lifetime {
+                        if elided.contains(&&*lifetime) {
+                            *lifetime = Lifetime::new("'_", lifetime.apostrophe);
+                        }
+                    }
+                }
+            }
+        }
+        Cow::Owned(elided_signature)
+    }
+
     fn receiver(&mut self, receiver: &Receiver) {
         self.outer_attrs(&receiver.attrs);
         if let Some((_ampersand, lifetime)) = &receiver.reference {
@@ -746,3 +833,183 @@ impl Printer {
         self.word("self");
     }
 }
+
+// Items in declaration order, or grouped into imports, modules, consts and
+// statics, types, traits, impls, then functions if `canonical`.
+pub fn items_in_order(items: &[Item], canonical: bool) -> Vec<&Item> {
+    if canonical {
+        canonical_item_order(items)
+    } else {
+        items.iter().collect()
+    }
+}
+
+// Within each group, items with a name are sorted by it, and imports and
+// impls keep their relative order. Items carrying `#[cfg]` or `#[cfg_attr]`,
+// macro invocations and verbatim items stay in place and nothing moves across
+// them, since a macro may refer to items declared before it.
+fn canonical_item_order(items: &[Item]) -> Vec<&Item> {
+    fn sort_key(item: &Item) -> Option<(u8, String)> {
+        let (rank, ident, attrs) = match item {
+            Item::ExternCrate(item) => (0, None, &item.attrs),
+            Item::Use(item) => (0, None, &item.attrs),
+            Item::Mod(item) => (1, Some(&item.ident), &item.attrs),
+            Item::Const(item) => (2, Some(&item.ident), &item.attrs),
+            Item::Static(item) => (2, Some(&item.ident), &item.attrs),
+            Item::Enum(item) => (3, Some(&item.ident), &item.attrs),
+            Item::Struct(item) => (3, Some(&item.ident), &item.attrs),
+            Item::Type(item) => (3, Some(&item.ident), &item.attrs),
+            Item::Union(item) => (3, Some(&item.ident), &item.attrs),
+            Item::Trait(item) => (4, Some(&item.ident), &item.attrs),
+            Item::TraitAlias(item) => (4, Some(&item.ident), &item.attrs),
+            Item::Impl(item) => (5, None, &item.attrs),
+            Item::ForeignMod(item) => (6, None, &item.attrs),
+            Item::Fn(item) => (6, Some(&item.sig.ident), &item.attrs),
+            _ => return None,
+        };
+        if attr::is_cfg_gated(attrs) {
+            None
+        } else {
+            Some((rank, ident.map_or_else(String::new, Ident::to_string)))
+        }
+    }
+
+    let mut ordered: Vec<&Item> = items.iter().collect();
+    for run in ordered.split_mut(|item| sort_key(item).is_none()) {
+        run.sort_by_cached_key(|item| sort_key(item));
+    }
+    ordered
+}
+
+// Associated consts, then types, then methods, each group sorted by name.
+// Items that are cfg-gated or macro-generated stay where they are, and only
+// the runs of items between them get sorted.
+fn canonical_impl_item_order(items: &[ImplItem]) -> Vec<&ImplItem> {
+    fn sort_key(impl_item: &ImplItem) -> Option<(u8, String)> {
+        let (rank, ident, attrs) = match impl_item {
+            ImplItem::Const(item) => (0, &item.ident, &item.attrs),
+            ImplItem::Type(item) => (1, &item.ident, &item.attrs),
+            ImplItem::Method(item) => (2, &item.sig.ident, &item.attrs),
+            _ => return None,
+        };
+        if attr::is_cfg_gated(attrs) {
+            None
+        } else {
+            Some((rank, ident.to_string()))
+        }
+    }
+
+    let mut ordered: Vec<&ImplItem> = items.iter().collect();
+    for run in ordered.split_mut(|impl_item| sort_key(impl_item).is_none()) {
+        run.sort_by_cached_key(|impl_item| sort_key(impl_item));
+    }
+    ordered
+}
+
+// Parses verbatim item tokens again, if they are in fact an ordinary item.
+#[cfg(feature = "parsing")]
+fn reparse_item(tokens: &TokenStream) -> Option<Item> {
+    match syn::parse2(tokens.clone()) {
+        Ok(Item::Verbatim(_)) | Err(_) => None,
+        Ok(item) => Some(item),
+    }
+}
+
+#[cfg(not(feature = "parsing"))]
+fn reparse_item(tokens: &TokenStream) -> Option<Item> {
+    let _ = tokens;
+    None
+}
+
+// The lifetimes of the arguments of a function that are references.
+fn reference_args(signature: &Signature) -> impl Iterator<Item = &Lifetime> {
+    signature.inputs.iter().filter_map(|input| match input {
+        FnArg::Typed(arg) => match &*arg.ty {
+            Type::Reference(ty) => ty.lifetime.as_ref(),
+            _ => None,
+        },
+        FnArg::Receiver(_) => None,
+    })
+}
+
+// Number of times a lifetime is named in a function's signature, including
+// its where clause, and in its body. Lifetimes in the tokens of macro calls
+// and verbatim syntax count too.
+fn lifetime_uses(signature: &Signature, body: Option<&Block>, lifetime: &Lifetime) -> usize {
+    let mut uses = LifetimeUses { lifetime, count: 0 };
+    uses.visit_signature(signature);
+    if let Some(body) = body {
+        uses.visit_block(body);
+    }
+    uses.count
+}
+
+struct LifetimeUses<'a> {
+    lifetime: &'a Lifetime,
+    count: usize,
+}
+
+impl LifetimeUses<'_> {
+    fn tokens(&mut self, tokens: &TokenStream) {
+        let mut after_apostrophe = false;
+        for tt in tokens.clone() {
+            after_apostrophe = match tt {
+                TokenTree::Group(group) => {
+                    self.tokens(&group.stream());
+                    false
+                }
+                TokenTree::Ident(ident) => {
+                    if after_apostrophe && ident == self.lifetime.ident {
+                        self.count += 1;
+                    }
+                    false
+                }
+                TokenTree::Punct(punct) => {
+                    punct.as_char() == '\'' && punct.spacing() == Spacing::Joint
+                }
+                TokenTree::Literal(_) => false,
+            };
+        }
+    }
+}
+
+impl<'ast> Visit<'ast> for LifetimeUses<'_> {
+    fn visit_lifetime(&mut self, lifetime: &'ast Lifetime) {
+        if lifetime == self.lifetime {
+            self.count += 1;
+        }
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        visit::visit_macro(self, mac);
+        self.tokens(&mac.tokens);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match expr {
+            Expr::Verbatim(tokens) => self.tokens(tokens),
+            _ => visit::visit_expr(self, expr),
+        }
+    }
+
+    fn visit_item(&mut self, item: &'ast Item) {
+        match item {
+            Item::Verbatim(tokens) => self.tokens(tokens),
+            _ => visit::visit_item(self, item),
+        }
+    }
+
+    fn visit_pat(&mut self, pat: &'ast Pat) {
+        match pat {
+            Pat::Verbatim(tokens) => self.tokens(tokens),
+            _ => visit::visit_pat(self, pat),
+        }
+    }
+
+    fn visit_type(&mut self, ty: &'ast Type) {
+        match ty {
+            Type::Verbatim(tokens) => self.tokens(tokens),
+            _ => visit::visit_type(self, ty),
+        }
+    }
+}
@@ -194,11 +194,41 @@
 //!     }
 //! };
 //!
+//! # #[cfg(feature = "full")]
 //! fn main() {
 //!     let syntax_tree = syn::parse_file(INPUT).unwrap();
 //!     let formatted = prettyplease::unparse(&syntax_tree);
 //!     print!("{}", formatted);
 //! }
+//! #
+//! # #[cfg(not(feature = "full"))]
+//! # fn main() {}
+//! ```
+//!
+//! <br>
+//!
+//! # Cargo features
+//!
+//! - **`full`** *(enabled by default)* &mdash; Formatting of the complete
+//!   Rust grammar, including function bodies, statements, expressions, and
+//!   patterns. Requires syn's `full` feature. Enables [`unparse`],
+//!   [`unparse_with`], [`unparse_item`], [`unparse_stmt`], and
+//!   [`unparse_expr`].
+//!
+//! - **`parsing`** &mdash; Enables `unparse_fragment`, which parses a snippet
+//!   of source code before formatting it, and formats an `Item::Verbatim`
+//!   in full when its tokens parse as an ordinary item. Requires syn's
+//!   `parsing` feature.
+//!
+//! With `default-features = false`, prettyplease compiles against syn's
+//! lighter `derive` feature and skips the expression, statement, and pattern
+//! printers. Only the entry points that work on syn's derive-level syntax
+//! tree are available: [`unparse_derive_input`] and [`unparse_type`].
+//!
+//! ```toml
+//! [dependencies]
+//! prettyplease = { version = "0.1", default-features = false }
+//! syn = { version = "1", default-features = false, features = ["derive", "parsing"] }
 //! ```
 //!
 //! <br>
@@ -338,25 +368,40 @@
 
 mod algorithm;
 mod attr;
+mod color;
+mod config;
 mod convenience;
 mod data;
 mod expr;
+#[cfg(feature = "full")]
 mod file;
 mod generics;
+#[cfg(feature = "full")]
 mod item;
 mod iter;
 mod lifetime;
 mod lit;
 mod mac;
+#[cfg(feature = "full")]
 mod pat;
 mod path;
 mod ring;
+#[cfg(feature = "full")]
 mod stmt;
 mod token;
 mod ty;
 
 use crate::algorithm::Printer;
-use syn::File;
+use syn::{DeriveInput, Type};
+#[cfg(feature = "full")]
+use {
+    std::borrow::Cow,
+    std::ops::Range,
+    syn::{Expr, File, Item, Stmt},
+};
+
+pub use crate::color::{Color, ColorScheme};
+pub use crate::config::{BraceStyle, Config, EmptyBlockStyle, HexCase, ItemOrder};
 
 // Target line width.
 const MARGIN: isize = 89;
@@ -367,8 +412,325 @@ const INDENT: isize = 4;
 // Every line is allowed at least this much space, even if highly indented.
 const MIN_SPACE: isize = 60;
 
+#[cfg(feature = "full")]
 pub fn unparse(file: &File) -> String {
-    let mut p = Printer::new();
-    p.file(file);
+    unparse_with(file, &Config::default())
+}
+
+#[cfg(feature = "full")]
+pub fn unparse_with(file: &File, config: &Config) -> String {
+    let mut p = Printer::new(config);
+    p.file(&transformed(file, config));
+    p.eof()
+}
+
+/// Format many files with the same configuration, reusing one printer's
+/// buffers between them. Each string is identical to what [`unparse_with`]
+/// returns for that file.
+#[cfg(feature = "full")]
+pub fn unparse_all<'a>(files: impl IntoIterator<Item = &'a File>, config: &Config) -> Vec<String> {
+    let mut p = Printer::new(config);
+    files
+        .into_iter()
+        .map(|file| {
+            p.file(&transformed(file, config));
+            p.take_output()
+        })
+        .collect()
+}
+
+/// Format a file like [`unparse`], also returning the byte range of the
+/// output taken up by each top-level item, in order. A range covers the
+/// item's attributes and doc comments but not the newline after it.
+///
+/// ```
+/// let file = syn::parse_file("use std::fmt; struct S;").unwrap();
+/// let (formatted, spans) = prettyplease::unparse_with_spans(&file);
+/// assert_eq!(formatted, prettyplease::unparse(&file));
+/// assert_eq!(&formatted[spans[0].clone()], "use std::fmt;");
+/// assert_eq!(&formatted[spans[1].clone()], "struct S;");
+/// ```
+#[cfg(feature = "full")]
+pub fn unparse_with_spans(file: &File) -> (String, Vec<Range<usize>>) {
+    let mut p = Printer::new(&Config::default());
+    p.file_with_spans(file)
+}
+
+/// Format a file like [`unparse_with`], also returning the numbers of the
+/// lines of the output, counting from 1, that are wider than the configured
+/// [`max_width`](Config::max_width). Those lines are left as they are, since
+/// they have nowhere to break, as with a long string literal.
+///
+/// ```
+/// let src = format!("const S: &str = \"{}\";", "x".repeat(200));
+/// let file = syn::parse_file(&src).unwrap();
+/// let config = prettyplease::Config::new();
+/// let (formatted, overlong) = prettyplease::unparse_checked(&file, &config);
+/// assert_eq!(formatted, prettyplease::unparse_with(&file, &config));
+/// assert_eq!(overlong, [2]); // the literal, on the line after `const S: &str =`
+/// ```
+#[cfg(feature = "full")]
+pub fn unparse_checked(file: &File, config: &Config) -> (String, Vec<usize>) {
+    let formatted = unparse_with(file, config);
+    let overlong = formatted
+        .lines()
+        .enumerate()
+        .filter(|(_i, line)| line_width(line, config) > config.max_width)
+        .map(|(i, _line)| i + 1)
+        .collect();
+    (formatted, overlong)
+}
+
+// Width of a line as displayed, with tabs as wide as the configured tab width
+// and color escape sequences taking up no space.
+#[cfg(feature = "full")]
+fn line_width(line: &str, config: &Config) -> usize {
+    let mut width = 0;
+    let mut chars = line.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\t' => width += config.tab_width,
+            '\x1b' => {
+                for ch in chars.by_ref() {
+                    if ch == 'm' {
+                        break;
+                    }
+                }
+            }
+            _ => width += 1,
+        }
+    }
+    width
+}
+
+#[cfg(feature = "full")]
+fn transformed<'a>(file: &'a File, config: &Config) -> Cow<'a, File> {
+    if config.transforms.is_empty() {
+        return Cow::Borrowed(file);
+    }
+    let mut file = file.clone();
+    for transform in &config.transforms {
+        transform(&mut file);
+    }
+    Cow::Owned(file)
+}
+
+/// Format a single item, followed by a newline, exactly as it would appear at
+/// the top level of a file passed to [`unparse`].
+///
+/// The value of a const or static moves to the line after the `=` when not
+/// even its first line fits after it, and a type too long for its line
+/// wraps.
+///
+/// ```
+/// let item = syn::parse_str(
+///     "impl S { const MAX_VALUE_FOR_THING: SomeLongTypeName<WithParams> = \
+///      complicated_expression_call(argument_one, argument_two); }",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     prettyplease::unparse_item(&item),
+///     "impl S {
+///     const MAX_VALUE_FOR_THING: SomeLongTypeName<WithParams> =
+///         complicated_expression_call(argument_one, argument_two);
+/// }
+/// ",
+/// );
+///
+/// let item = syn::parse_str("trait T { const N: usize; }").unwrap();
+/// assert_eq!(prettyplease::unparse_item(&item), "trait T {\n    const N: usize;\n}\n");
+///
+/// let item = syn::parse_str(
+///     "static mut REGISTRY: Mutex<HashMap<LongKeyTypeXXXXXXXX, LongValueTypeXXXXXXXXXXXX>> = \
+///      Mutex::new(HashMap::new());",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     prettyplease::unparse_item(&item),
+///     "static mut REGISTRY: Mutex<HashMap<LongKeyTypeXXXXXXXX, LongValueTypeXXXXXXXXXXXX>> =
+///     Mutex::new(HashMap::new());
+/// ",
+/// );
+///
+/// let item = syn::parse_str(
+///     "static TABLE: [LongElementTypeXXXXXXXXXXXX<WithGenerics, AndMoreXXXXXXXXXXXXXXXXXXXX>; 64] = \
+///      [DEFAULT_ENTRY_VALUE; 64];",
+/// )
+/// .unwrap();
+/// assert_eq!(
+///     prettyplease::unparse_item(&item),
+///     "static TABLE: [LongElementTypeXXXXXXXXXXXX<\n    WithGenerics,\n    AndMoreXXXXXXXXXXXXXXXXXXXX,\n>; 64] = [DEFAULT_ENTRY_VALUE; 64];\n",
+/// );
+/// ```
+#[cfg(feature = "full")]
+pub fn unparse_item(item: &Item) -> String {
+    let mut p = Printer::new(&Config::default());
+    p.cbox(0);
+    p.item(item);
+    p.end();
+    p.eof()
+}
+
+/// Format a single statement, followed by a newline, as it would appear in a
+/// block at the top level of indentation.
+///
+/// A macro statement delimited by parentheses or brackets always ends in a
+/// semicolon unless it is the value at the end of its block. One delimited
+/// by braces keeps a semicolon exactly where it was written, which at the end
+/// of a block decides whether the macro is the block's value.
+///
+/// Attributes of a `let` go on their own lines, and the value moves to the
+/// line after the `=` when not even its first line fits after it. A block
+/// value of any flavor keeps its keyword and opening brace on the `let` line.
+///
+/// ```
+/// let src = "\
+/// fn paren() {
+///     a!();
+///     a!()
+/// }
+/// fn paren_semi() {
+///     a!();
+///     a!();
+/// }
+/// fn bracket() {
+///     b![];
+///     b![]
+/// }
+/// fn bracket_semi() {
+///     b![];
+///     b![];
+/// }
+/// fn brace() {
+///     c! {}
+///     c! {}
+/// }
+/// fn brace_semi() {
+///     c! {};
+///     c! {};
+/// }
+/// ";
+/// let file = syn::parse_file(src).unwrap();
+/// assert_eq!(prettyplease::unparse(&file), src);
+///
+/// let src = "\
+/// fn f() {
+///     #[allow(unused)]
+///     let xxxxxxxxxx: LongTypeNameXXXXXXXXXXXXX =
+///         long_initializer_function_name_xxxxxxxxxxx(a);
+/// }
+/// ";
+/// let file = syn::parse_file(src).unwrap();
+/// assert_eq!(prettyplease::unparse(&file), src);
+///
+/// let src = "\
+/// fn f() {
+///     let a = async { g().await };
+///     let b = async move { g(x).await };
+///     let c = const { N * 2 };
+///     let d = try { g()? };
+///     let xxxxxxxxxxxxxxxxxxxx = const {
+///         some_function_with_long_name(argument_one, argument_two)
+///     };
+/// }
+/// ";
+/// let file = syn::parse_file(src).unwrap();
+/// assert_eq!(prettyplease::unparse(&file), src);
+/// ```
+///
+/// With the `parsing` feature, the statements of an inline const block are
+/// formatted like those of any other block, rather than spaced out as tokens.
+///
+/// ```
+/// # #[cfg(feature = "parsing")] {
+/// let src = "\
+/// fn f() {
+///     let e = const {
+///         let n = N;
+///         n * 2
+///     };
+/// }
+/// ";
+/// let file = syn::parse_file(src).unwrap();
+/// assert_eq!(prettyplease::unparse(&file), src);
+/// # }
+/// ```
+#[cfg(feature = "full")]
+pub fn unparse_stmt(stmt: &Stmt) -> String {
+    let mut p = Printer::new(&Config::default());
+    p.cbox(0);
+    p.stmt(stmt);
+    p.end();
+    p.eof()
+}
+
+/// Format an expression on a single line if it fits, with no trailing
+/// newline.
+#[cfg(feature = "full")]
+pub fn unparse_expr(expr: &Expr) -> String {
+    let mut p = Printer::new(&Config::default());
+    p.cbox(0);
+    p.expr(expr);
+    p.end();
+    p.eof()
+}
+
+/// Parse a snippet of source code as an item, a statement, an expression, or
+/// a type, trying each in that order, and format the first that succeeds with
+/// [`unparse_item`], [`unparse_stmt`], [`unparse_expr`], or [`unparse_type`].
+/// Requires the `parsing` feature.
+///
+/// # Errors
+///
+/// If the snippet parses as none of them, the error of each attempt is
+/// returned combined into one `syn::Error`.
+///
+/// ```
+/// # use prettyplease::unparse_fragment;
+/// #
+/// assert_eq!(unparse_fragment("struct S{x:u8}").unwrap(), "struct S {\n    x: u8,\n}\n");
+/// assert_eq!(unparse_fragment("let x=1;").unwrap(), "let x = 1;\n");
+/// assert_eq!(unparse_fragment("x+1").unwrap(), "x + 1");
+/// assert_eq!(unparse_fragment("Vec < u8 >").unwrap(), "Vec<u8>");
+/// assert!(unparse_fragment("struct {").is_err());
+/// ```
+#[cfg(feature = "parsing")]
+pub fn unparse_fragment(src: &str) -> syn::Result<String> {
+    let mut error = match syn::parse_str::<Item>(src) {
+        Ok(item) => return Ok(unparse_item(&item)),
+        Err(error) => error,
+    };
+    match syn::parse_str::<Stmt>(src) {
+        Ok(stmt) => return Ok(unparse_stmt(&stmt)),
+        Err(other) => error.combine(other),
+    }
+    match syn::parse_str::<Expr>(src) {
+        Ok(expr) => return Ok(unparse_expr(&expr)),
+        Err(other) => error.combine(other),
+    }
+    match syn::parse_str::<Type>(src) {
+        Ok(ty) => Ok(unparse_type(&ty)),
+        Err(other) => {
+            error.combine(other);
+            Err(error)
+        }
+    }
+}
+
+/// Format a struct, enum, or union as parsed by a derive macro, followed by a
+/// newline. Available without the `full` feature.
+pub fn unparse_derive_input(input: &DeriveInput) -> String {
+    let mut p = Printer::new(&Config::default());
+    p.cbox(0);
+    p.derive_input(input);
+    p.end();
+    p.eof()
+}
+
+/// Format a type on a single line if it fits, with no trailing newline.
+/// Available without the `full` feature.
+pub fn unparse_type(ty: &Type) -> String {
+    let mut p = Printer::new(&Config::default());
+    p.ty(ty);
     p.eof()
 }
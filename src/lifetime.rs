@@ -1,9 +1,9 @@
 use crate::algorithm::Printer;
+use crate::color::Category;
 use syn::Lifetime;
 
 impl Printer {
     pub fn lifetime(&mut self, lifetime: &Lifetime) {
-        self.word("'");
-        self.ident(&lifetime.ident);
+        self.highlighted(Category::Ident, format!("'{}", lifetime.ident));
     }
 }
@@ -1,9 +1,19 @@
 use crate::algorithm::Printer;
+use crate::color::Category;
+use crate::HexCase;
 use proc_macro2::Literal;
 use syn::{Lit, LitBool, LitByte, LitByteStr, LitChar, LitFloat, LitInt, LitStr};
 
 impl Printer {
     pub fn lit(&mut self, lit: &Lit) {
+        if let Some(text) = self
+            .config
+            .lit_filter
+            .as_ref()
+            .and_then(|filter| filter(lit))
+        {
+            return self.highlighted(Category::Literal, text);
+        }
         match lit {
             Lit::Str(lit) => self.lit_str(lit),
             Lit::ByteStr(lit) => self.lit_byte_str(lit),
@@ -17,34 +27,120 @@ impl Printer {
     }
 
     pub fn lit_str(&mut self, lit: &LitStr) {
-        self.word(lit.token().to_string());
+        self.quoted_literal(lit.token().to_string());
     }
 
     fn lit_byte_str(&mut self, lit: &LitByteStr) {
-        self.word(lit.token().to_string());
+        self.quoted_literal(lit.token().to_string());
     }
 
     fn lit_byte(&mut self, lit: &LitByte) {
-        self.word(lit.token().to_string());
+        self.quoted_literal(lit.token().to_string());
     }
 
     fn lit_char(&mut self, lit: &LitChar) {
-        self.word(lit.token().to_string());
+        self.quoted_literal(lit.token().to_string());
     }
 
     fn lit_int(&mut self, lit: &LitInt) {
-        self.word(lit.token().to_string());
+        let mut repr = lit.token().to_string();
+        if self.config.normalize_numeric_suffix_spacing {
+            repr = normalize_suffix(&repr, lit.suffix());
+        }
+        let digits = repr.len() - lit.suffix().len();
+        if repr.starts_with("0x") {
+            match self.config.hex_case {
+                HexCase::Preserve => {}
+                HexCase::Upper => repr[2..digits].make_ascii_uppercase(),
+                HexCase::Lower => repr[2..digits].make_ascii_lowercase(),
+            }
+        }
+        self.highlighted(Category::Literal, repr);
     }
 
     fn lit_float(&mut self, lit: &LitFloat) {
-        self.word(lit.token().to_string());
+        let mut repr = lit.token().to_string();
+        if self.config.normalize_numeric_suffix_spacing {
+            repr = normalize_suffix(&repr, lit.suffix());
+        }
+        self.highlighted(Category::Literal, repr);
     }
 
     fn lit_bool(&mut self, lit: &LitBool) {
-        self.word(if lit.value { "true" } else { "false" });
+        self.highlighted(Category::Literal, if lit.value { "true" } else { "false" });
     }
 
     fn lit_verbatim(&mut self, token: &Literal) {
-        self.word(token.to_string());
+        self.highlighted(Category::Literal, token.to_string());
+    }
+
+    fn quoted_literal(&mut self, repr: String) {
+        let repr = if self.config.reformat_string_literals {
+            unescape_printable(&repr)
+        } else {
+            repr
+        };
+        self.highlighted(Category::Literal, repr);
+    }
+}
+
+// Glues a primitive type suffix directly onto the digits and lowercases it,
+// so `1_u8` and `1U8` both print as `1u8`. Other suffixes are left alone.
+fn normalize_suffix(repr: &str, suffix: &str) -> String {
+    let lowercase = suffix.to_ascii_lowercase();
+    let primitive = match lowercase.as_str() {
+        "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+        | "usize" | "f32" | "f64" => true,
+        _ => false,
+    };
+    if !primitive {
+        return repr.to_owned();
+    }
+    let digits = repr[..repr.len() - suffix.len()].trim_end_matches('_');
+    digits.to_owned() + &lowercase
+}
+
+// Replaces each `\x41` or `\u{41}` escape of a printable ASCII character in a
+// string, byte string, char or byte literal by the character itself, leaving
+// every other escape as written. Backslashes and the literal's own quote stay
+// escaped. Raw strings have no escapes and are returned unchanged.
+fn unescape_printable(repr: &str) -> String {
+    if repr.starts_with('r') || repr.starts_with("br") {
+        return repr.to_owned();
+    }
+    let quote = if repr.starts_with('\'') || repr.starts_with("b'") {
+        '\''
+    } else {
+        '"'
+    };
+    let mut unescaped = String::with_capacity(repr.len());
+    let mut rest = repr;
+    while let Some(backslash) = rest.find('\\') {
+        unescaped.push_str(&rest[..backslash]);
+        let escape = &rest[backslash + 1..];
+        let (digits, len) = if let Some(hex) = escape.strip_prefix('x') {
+            (hex.get(..2).unwrap_or(""), 4)
+        } else if let Some(braced) = escape.strip_prefix("u{") {
+            let end = braced.find('}').unwrap_or(0);
+            (&braced[..end], end + 4)
+        } else {
+            ("", 0)
+        };
+        let printable = u32::from_str_radix(&digits.replace('_', ""), 16)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|&ch| (' '..='~').contains(&ch) && ch != '\\' && ch != quote);
+        if let Some(ch) = printable {
+            unescaped.push(ch);
+            rest = &rest[backslash + len..];
+        } else {
+            // Copy the backslash and the character after it, so that an
+            // escaped backslash isn't mistaken for the start of an escape.
+            let len = escape.chars().next().map_or(1, |ch| 1 + ch.len_utf8());
+            unescaped.push_str(&rest[backslash..backslash + len]);
+            rest = &rest[backslash + len..];
+        }
     }
+    unescaped.push_str(rest);
+    unescaped
 }
@@ -28,17 +28,24 @@ impl Printer {
             MacroDelimiter::Bracket(_) => ("[", "]", Self::zerobreak as fn(&mut Self)),
         };
         self.word(open);
-        self.cbox(INDENT);
-        delimiter_break(self);
-        self.ibox(0);
-        self.macro_rules_tokens(mac.tokens.clone(), false);
-        self.end();
-        delimiter_break(self);
-        self.offset(-INDENT);
-        self.end();
+        if mac.tokens.is_empty() {
+            if let MacroDelimiter::Brace(_) = mac.delimiter {
+                self.empty_block_interior();
+            }
+        } else {
+            self.cbox(INDENT);
+            delimiter_break(self);
+            self.ibox(0);
+            self.macro_rules_tokens(mac.tokens.clone(), false);
+            self.end();
+            delimiter_break(self);
+            self.offset(-INDENT);
+            self.end();
+        }
         self.word(close);
     }
 
+    #[cfg(feature = "full")]
     pub fn mac_semi_if_needed(&mut self, delimiter: &MacroDelimiter) {
         match delimiter {
             MacroDelimiter::Paren(_) | MacroDelimiter::Bracket(_) => self.word(";"),
@@ -47,6 +54,33 @@ impl Printer {
     }
 
     fn macro_rules(&mut self, name: &Ident, rules: &TokenStream) {
+        self.word("macro_rules! ");
+        self.ident(name);
+        self.macro_arms(rules, ';');
+    }
+
+    // The body of a declarative macro 2.0 after its name: either a single
+    // rule given as `(args) { body }`, or `{ (matcher) => { body }, ... }`.
+    #[cfg(feature = "full")]
+    pub fn macro2_rules(&mut self, rules: &TokenStream) {
+        let mut tokens = rules.clone().into_iter().map(Token::from);
+        match (tokens.next(), tokens.next(), tokens.next()) {
+            (Some(Token::Group(Delimiter::Brace, arms)), None, None) => {
+                self.macro_arms(&arms, ',');
+            }
+            (
+                Some(Token::Group(Delimiter::Parenthesis, matcher)),
+                Some(Token::Group(Delimiter::Brace, expander)),
+                None,
+            ) => {
+                self.macro_matcher(Delimiter::Parenthesis, matcher);
+                self.macro_expander(expander);
+            }
+            _ => unimplemented!("bad macro syntax"),
+        }
+    }
+
+    fn macro_arms(&mut self, rules: &TokenStream, separator: char) {
         enum State {
             Start,
             Matcher,
@@ -57,8 +91,6 @@ impl Printer {
 
         use State::*;
 
-        self.word("macro_rules! ");
-        self.ident(name);
         self.word(" {");
         self.cbox(INDENT);
         self.hardbreak_if_nonempty();
@@ -67,18 +99,7 @@ impl Printer {
             let token = Token::from(tt);
             match (state, token) {
                 (Start, Token::Group(delimiter, stream)) => {
-                    self.delimiter_open(delimiter);
-                    if !stream.is_empty() {
-                        self.cbox(INDENT);
-                        self.zerobreak();
-                        self.ibox(0);
-                        self.macro_rules_tokens(stream, true);
-                        self.end();
-                        self.zerobreak();
-                        self.offset(-INDENT);
-                        self.end();
-                    }
-                    self.delimiter_close(delimiter);
+                    self.macro_matcher(delimiter, stream);
                     state = Matcher;
                 }
                 (Matcher, Token::Punct('=', Spacing::Joint)) => {
@@ -90,23 +111,11 @@ impl Printer {
                     state = Greater;
                 }
                 (Greater, Token::Group(_delimiter, stream)) => {
-                    self.word(" {");
-                    self.neverbreak();
-                    if !stream.is_empty() {
-                        self.cbox(INDENT);
-                        self.hardbreak();
-                        self.ibox(0);
-                        self.macro_rules_tokens(stream, false);
-                        self.end();
-                        self.hardbreak();
-                        self.offset(-INDENT);
-                        self.end();
-                    }
-                    self.word("}");
+                    self.macro_expander(stream);
                     state = Expander;
                 }
-                (Expander, Token::Punct(';', Spacing::Alone)) => {
-                    self.word(";");
+                (Expander, Token::Punct(ch, Spacing::Alone)) if ch == separator => {
+                    self.token_punct(separator);
                     self.hardbreak();
                     state = Start;
                 }
@@ -116,7 +125,7 @@ impl Printer {
         match state {
             Start => {}
             Expander => {
-                self.word(";");
+                self.token_punct(separator);
                 self.hardbreak();
             }
             _ => self.hardbreak(),
@@ -126,7 +135,38 @@ impl Printer {
         self.word("}");
     }
 
-    fn macro_rules_tokens(&mut self, stream: TokenStream, matcher: bool) {
+    fn macro_matcher(&mut self, delimiter: Delimiter, stream: TokenStream) {
+        self.delimiter_open(delimiter);
+        if !stream.is_empty() {
+            self.cbox(INDENT);
+            self.zerobreak();
+            self.ibox(0);
+            self.macro_rules_tokens(stream, true);
+            self.end();
+            self.zerobreak();
+            self.offset(-INDENT);
+            self.end();
+        }
+        self.delimiter_close(delimiter);
+    }
+
+    fn macro_expander(&mut self, stream: TokenStream) {
+        self.word(" {");
+        self.neverbreak();
+        if !stream.is_empty() {
+            self.cbox(INDENT);
+            self.hardbreak();
+            self.ibox(0);
+            self.macro_rules_tokens(stream, false);
+            self.end();
+            self.hardbreak();
+            self.offset(-INDENT);
+            self.end();
+        }
+        self.word("}");
+    }
+
+    pub fn macro_rules_tokens(&mut self, stream: TokenStream, matcher: bool) {
         #[derive(PartialEq)]
         enum State {
             Start,
@@ -197,6 +237,18 @@ impl Printer {
             state = next_state;
         }
     }
+
+    // Syntax that syn keeps as verbatim tokens and that has no dedicated
+    // printer is spaced out like the body of a macro, unless error recovery is
+    // off, in which case it panics.
+    pub fn verbatim(&mut self, kind: &str, tokens: &TokenStream) {
+        if !self.config.error_recovery {
+            unimplemented!("{}::Verbatim `{}`", kind, tokens);
+        }
+        self.ibox(0);
+        self.macro_rules_tokens(tokens.clone(), false);
+        self.end();
+    }
 }
 
 fn is_keyword(ident: &Ident) -> bool {
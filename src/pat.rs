@@ -1,7 +1,9 @@
 use crate::algorithm::Printer;
+use crate::expr;
 use crate::iter::IterDelimited;
+use crate::token::Token;
 use crate::INDENT;
-use proc_macro2::TokenStream;
+use proc_macro2::{Spacing, TokenStream, TokenTree};
 use syn::{
     FieldPat, Pat, PatBox, PatIdent, PatLit, PatMacro, PatOr, PatPath, PatRange, PatReference,
     PatRest, PatSlice, PatStruct, PatTuple, PatTupleStruct, PatType, PatWild, RangeLimits,
@@ -26,9 +28,6 @@ impl Printer {
             Pat::Type(pat) => self.pat_type(pat),
             Pat::Verbatim(pat) => self.pat_verbatim(pat),
             Pat::Wild(pat) => self.pat_wild(pat),
-            #[cfg(test)]
-            Pat::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown Pat"),
         }
     }
@@ -36,7 +35,7 @@ impl Printer {
     fn pat_box(&mut self, pat: &PatBox) {
         self.outer_attrs(&pat.attrs);
         self.word("box ");
-        self.pat(&pat.pat);
+        self.pat_after_prefix(&pat.pat);
     }
 
     fn pat_ident(&mut self, pat: &PatIdent) {
@@ -50,7 +49,7 @@ impl Printer {
         self.ident(&pat.ident);
         if let Some((_at_token, subpat)) = &pat.subpat {
             self.word(" @ ");
-            self.pat(subpat);
+            self.pat_after_prefix(subpat);
         }
     }
 
@@ -70,7 +69,7 @@ impl Printer {
             if !case.is_first {
                 self.word(" | ");
             }
-            self.pat(&case);
+            self.pat_without_parens(&case);
         }
     }
 
@@ -82,6 +81,9 @@ impl Printer {
     fn pat_range(&mut self, pat: &PatRange) {
         self.outer_attrs(&pat.attrs);
         self.expr(&pat.lo);
+        if expr::ends_with_bare_float_dot(&pat.lo) {
+            self.nbsp();
+        }
         match &pat.limits {
             RangeLimits::HalfOpen(_) => self.word(".."),
             RangeLimits::Closed(_) => self.word("..="),
@@ -95,7 +97,7 @@ impl Printer {
         if pat.mutability.is_some() {
             self.word("mut ");
         }
-        self.pat(&pat.pat);
+        self.pat_after_prefix(&pat.pat);
     }
 
     fn pat_rest(&mut self, pat: &PatRest) {
@@ -107,7 +109,7 @@ impl Printer {
         self.outer_attrs(&pat.attrs);
         self.word("[");
         for elem in pat.elems.iter().delimited() {
-            self.pat(&elem);
+            self.pat_without_parens(&elem);
             self.trailing_comma(elem.is_last);
         }
         self.word("]");
@@ -118,14 +120,14 @@ impl Printer {
         self.cbox(INDENT);
         self.path(&pat.path);
         self.word(" {");
-        self.space_if_nonempty();
+        self.struct_brace_space_if_nonempty();
         for field in pat.fields.iter().delimited() {
             self.field_pat(&field);
-            self.trailing_comma_or_space(field.is_last && pat.dot2_token.is_none());
+            self.struct_trailing_comma_or_space(field.is_last && pat.dot2_token.is_none());
         }
         if pat.dot2_token.is_some() {
             self.word("..");
-            self.space();
+            self.struct_brace_space();
         }
         self.offset(-INDENT);
         self.end();
@@ -133,13 +135,40 @@ impl Printer {
     }
 
     fn pat_tuple(&mut self, pat: &PatTuple) {
+        if self.config.remove_nested_parens && is_paren(pat) && pat.attrs.is_empty() {
+            let inner = without_parens(&pat.elems[0]);
+            if !needs_parens(inner) {
+                return self.pat(inner);
+            }
+            self.word("(");
+            self.pat(inner);
+            self.word(")");
+            return;
+        }
+        self.pat_tuple_fields(pat, true);
+    }
+
+    // A single-element tuple pattern needs its trailing comma to stay a tuple,
+    // while the fields of a tuple struct pattern such as `Some(x)` don't. Syn
+    // represents a parenthesized pattern `(x)` as a tuple pattern without the
+    // trailing comma.
+    fn pat_tuple_fields(&mut self, pat: &PatTuple, is_tuple: bool) {
         self.outer_attrs(&pat.attrs);
         self.word("(");
         self.cbox(INDENT);
         self.zerobreak();
         for elem in pat.elems.iter().delimited() {
-            self.pat(&elem);
-            self.trailing_comma(elem.is_last);
+            if is_tuple && is_paren(pat) {
+                self.pat(&elem);
+            } else {
+                self.pat_without_parens(&elem);
+            }
+            if is_tuple && pat.elems.len() == 1 && pat.elems.trailing_punct() {
+                self.word(",");
+                self.zerobreak();
+            } else {
+                self.trailing_comma(elem.is_last);
+            }
         }
         self.offset(-INDENT);
         self.end();
@@ -149,18 +178,64 @@ impl Printer {
     fn pat_tuple_struct(&mut self, pat: &PatTupleStruct) {
         self.outer_attrs(&pat.attrs);
         self.path(&pat.path);
-        self.pat_tuple(&pat.pat);
+        self.pat_tuple_fields(&pat.pat, false);
     }
 
     pub fn pat_type(&mut self, pat: &PatType) {
         self.outer_attrs(&pat.attrs);
         self.pat(&pat.pat);
-        self.word(": ");
+        self.type_annotation_colon();
         self.ty(&pat.ty);
     }
 
     fn pat_verbatim(&mut self, pat: &TokenStream) {
-        unimplemented!("Pat::Verbatim `{}`", pat);
+        // Syn represents the half-open range patterns `lo..`, `..hi` and
+        // `..=hi` as verbatim tokens.
+        let tokens: Vec<TokenTree> = pat.clone().into_iter().collect();
+        let range = tokens.windows(2).position(|pair| match pair {
+            [TokenTree::Punct(first), TokenTree::Punct(second)] => {
+                first.as_char() == '.'
+                    && first.spacing() == Spacing::Joint
+                    && second.as_char() == '.'
+            }
+            _ => false,
+        });
+        let (lo, rest) = match range {
+            Some(i) => tokens.split_at(i),
+            None => return self.verbatim("Pat", pat),
+        };
+        let (limits, hi) = match rest {
+            [_, TokenTree::Punct(dot), TokenTree::Punct(eq), hi @ ..]
+                if dot.spacing() == Spacing::Joint && eq.as_char() == '=' =>
+            {
+                ("..=", hi)
+            }
+            [_, _, hi @ ..] => ("..", hi),
+            _ => unreachable!(),
+        };
+        self.range_endpoint_tokens(lo);
+        if let Some(TokenTree::Literal(lit)) = lo.last() {
+            if lit.to_string().ends_with('.') {
+                self.nbsp();
+            }
+        }
+        self.word(limits);
+        self.range_endpoint_tokens(hi);
+    }
+
+    fn range_endpoint_tokens(&mut self, tokens: &[TokenTree]) {
+        let mut previous_is_word = false;
+        for tt in tokens {
+            let is_word = matches!(tt, TokenTree::Ident(_) | TokenTree::Literal(_));
+            if previous_is_word && is_word {
+                self.nbsp();
+            }
+            previous_is_word = is_word;
+            self.single_token(Token::from(tt.clone()), |printer, stream| {
+                let tokens: Vec<TokenTree> = stream.into_iter().collect();
+                printer.range_endpoint_tokens(&tokens);
+            });
+        }
     }
 
     fn pat_wild(&mut self, pat: &PatWild) {
@@ -174,6 +249,70 @@ impl Printer {
             self.member(&field_pat.member);
             self.word(": ");
         }
-        self.pat(&field_pat.pat);
+        self.pat_without_parens(&field_pat.pat);
+    }
+
+    // The pattern after `&`, `box` or `@`, where a binding with `ref`, `mut`
+    // or a subpattern keeps its parentheses: `&(mut x)` is different from
+    // `&mut x`.
+    fn pat_after_prefix(&mut self, pat: &Pat) {
+        match pat {
+            Pat::Tuple(tuple)
+                if self.config.remove_nested_parens
+                    && is_paren(tuple)
+                    && tuple.attrs.is_empty() =>
+            {
+                let inner = without_parens(&tuple.elems[0]);
+                if is_binding_with_mode(inner) {
+                    self.word("(");
+                    self.pat(inner);
+                    self.word(")");
+                } else {
+                    self.pat_tuple(tuple);
+                }
+            }
+            _ => self.pat(pat),
+        }
+    }
+
+    // The elements of a tuple, slice or tuple struct pattern, the fields of a
+    // struct pattern, the cases of an or-pattern and the pattern of a match
+    // arm may be any pattern, so no parentheses are needed around them.
+    pub fn pat_without_parens(&mut self, pat: &Pat) {
+        if self.config.remove_nested_parens {
+            self.pat(without_parens(pat));
+        } else {
+            self.pat(pat);
+        }
+    }
+}
+
+fn is_paren(pat: &PatTuple) -> bool {
+    pat.elems.len() == 1 && !pat.elems.trailing_punct()
+}
+
+fn without_parens(mut pat: &Pat) -> &Pat {
+    while let Pat::Tuple(tuple) = pat {
+        if !is_paren(tuple) || !tuple.attrs.is_empty() {
+            break;
+        }
+        pat = &tuple.elems[0];
+    }
+    pat
+}
+
+// Patterns that can't go without parentheses after `&`, `box` or `@`, or at
+// the top level of a `let` or a parameter, as in `&(a | b)` and `&(0..=9)`.
+fn needs_parens(pat: &Pat) -> bool {
+    matches!(
+        pat,
+        Pat::Or(_) | Pat::Range(_) | Pat::Type(_) | Pat::Verbatim(_),
+    )
+}
+
+fn is_binding_with_mode(pat: &Pat) -> bool {
+    match pat {
+        Pat::Ident(pat) => pat.by_ref.is_some() || pat.mutability.is_some() || pat.subpat.is_some(),
+        _ => false,
     }
 }
@@ -3,8 +3,8 @@ use crate::iter::IterDelimited;
 use crate::INDENT;
 use std::cmp;
 use syn::{
-    AngleBracketedGenericArguments, Binding, Constraint, Expr, GenericArgument,
-    ParenthesizedGenericArguments, Path, PathArguments, PathSegment, QSelf,
+    AngleBracketedGenericArguments, Binding, Constraint, Expr, ExprUnary, GenericArgument,
+    ParenthesizedGenericArguments, Path, PathArguments, PathSegment, QSelf, UnOp,
 };
 
 impl Printer {
@@ -43,14 +43,24 @@ impl Printer {
             GenericArgument::Const(expr) => {
                 match expr {
                     Expr::Lit(expr) => self.expr_lit(expr),
+                    #[cfg(feature = "full")]
                     Expr::Block(expr) => self.expr_block(expr),
                     // ERROR CORRECTION: Add braces to make sure that the
                     // generated code is valid.
-                    _ => {
+                    _ if self.config.error_recovery => {
                         self.word("{");
                         self.expr(expr);
                         self.word("}");
                     }
+                    Expr::Path(_) => self.expr(expr),
+                    Expr::Unary(ExprUnary {
+                        op: UnOp::Neg(_),
+                        expr: operand,
+                        ..
+                    }) if matches!(**operand, Expr::Lit(_)) => self.expr(expr),
+                    _ => {
+                        panic!("const generic argument requires braces, and error recovery is off")
+                    }
                 }
             }
         }
@@ -120,7 +130,7 @@ impl Printer {
         self.ibox(INDENT);
         for bound in constraint.bounds.iter().delimited() {
             if bound.is_first {
-                self.word(": ");
+                self.type_annotation_colon();
             } else {
                 self.space();
                 self.word("+ ");
@@ -153,13 +163,19 @@ impl Printer {
             }
         };
 
+        // A long qualified self type breaks before `as`, with the trait
+        // indented on the next line, like the type of a cast.
+        self.ibox(INDENT);
+        self.ibox(-INDENT);
         self.word("<");
         self.ty(&qself.ty);
+        self.end();
 
         let pos = cmp::min(qself.position, path.segments.len());
         let mut segments = path.segments.iter();
         if pos > 0 {
-            self.word(" as ");
+            self.space();
+            self.word("as ");
             for segment in segments.by_ref().take(pos).delimited() {
                 if !segment.is_first || path.leading_colon.is_some() {
                     self.word("::");
@@ -172,6 +188,7 @@ impl Printer {
         } else {
             self.word(">");
         }
+        self.end();
         for segment in segments {
             self.word("::");
             self.path_segment(segment);
@@ -1,5 +1,11 @@
 use crate::algorithm::Printer;
-use syn::{Expr, Stmt};
+use crate::expr;
+use proc_macro2::TokenStream;
+use std::mem;
+use syn::{
+    token, Expr, ExprAssign, ExprAssignOp, ExprAwait, ExprBinary, ExprCall, ExprCast, ExprField,
+    ExprIndex, ExprMethodCall, ExprParen, ExprRange, ExprTry, ExprType, Item, Stmt,
+};
 
 impl Printer {
     pub fn stmt(&mut self, stmt: &Stmt) {
@@ -10,18 +16,17 @@ impl Printer {
                 self.word("let ");
                 self.pat(&local.pat);
                 if let Some((_eq, init)) = &local.init {
-                    self.word(" = ");
-                    self.neverbreak();
-                    self.expr(init);
+                    self.initializer(init);
                 }
                 self.word(";");
                 self.end();
                 self.hardbreak();
             }
+            Stmt::Item(Item::Macro(item)) => self.stmt_macro(item),
             Stmt::Item(item) => self.item(item),
             Stmt::Expr(expr) => {
                 self.ibox(0);
-                self.expr(expr);
+                self.stmt_expr(expr);
                 if add_semi(expr) {
                     self.word(";");
                 }
@@ -35,7 +40,7 @@ impl Printer {
                     }
                 }
                 self.ibox(0);
-                self.expr(expr);
+                self.stmt_expr(expr);
                 if !remove_semi(expr) {
                     self.word(";");
                 }
@@ -44,6 +49,24 @@ impl Printer {
             }
         }
     }
+
+    // An expression statement that begins with a block-like expression, as in
+    // `{ x } + 1`, would parse as that block followed by a statement of its
+    // own, unless the block is parenthesized. Only a method call, field access,
+    // `.await` or `?` can follow the block directly.
+    fn stmt_expr(&mut self, expr: &Expr) {
+        let mut top = expr;
+        while let Expr::Group(group) = top {
+            top = &group.expr;
+        }
+        if self.config.preserve_block_expr_parens || !leading_block_like(top) {
+            self.expr(expr);
+        } else {
+            let mut expr = top.clone();
+            parenthesize_leading_block_like(&mut expr);
+            self.expr(&expr);
+        }
+    }
 }
 
 pub fn add_semi(expr: &Expr) -> bool {
@@ -70,3 +93,82 @@ fn remove_semi(expr: &Expr) -> bool {
         _ => false,
     }
 }
+
+fn is_block_like(expr: &Expr) -> bool {
+    match expr {
+        Expr::Group(group) => is_block_like(&group.expr),
+        _ => !expr::requires_terminator(expr),
+    }
+}
+
+// Postfix expressions the parser continues with after a block-like
+// expression at the start of a statement.
+fn continues_block_like(expr: &Expr) -> bool {
+    match expr {
+        Expr::Await(_) | Expr::Field(_) | Expr::MethodCall(_) | Expr::Try(_) => true,
+        _ => false,
+    }
+}
+
+// Whether an operand along the left edge of the expression, the ones printed
+// first, is block-like and followed by something the parser would not take
+// as a continuation of it.
+fn leading_block_like(expr: &Expr) -> bool {
+    match leading_operand(expr) {
+        Some(operand) if is_block_like(operand) => !continues_block_like(expr),
+        Some(operand) => leading_block_like(operand),
+        None => false,
+    }
+}
+
+fn parenthesize_leading_block_like(expr: &mut Expr) {
+    let continues = continues_block_like(expr);
+    if let Some(operand) = leading_operand_mut(expr) {
+        if !is_block_like(operand) {
+            parenthesize_leading_block_like(operand);
+        } else if !continues {
+            let block_like = mem::replace(operand, Expr::Verbatim(TokenStream::new()));
+            *operand = Expr::Paren(ExprParen {
+                attrs: Vec::new(),
+                paren_token: token::Paren::default(),
+                expr: Box::new(block_like),
+            });
+        }
+    }
+}
+
+fn leading_operand(expr: &Expr) -> Option<&Expr> {
+    match expr {
+        Expr::Assign(ExprAssign { left: e, .. })
+        | Expr::AssignOp(ExprAssignOp { left: e, .. })
+        | Expr::Await(ExprAwait { base: e, .. })
+        | Expr::Binary(ExprBinary { left: e, .. })
+        | Expr::Call(ExprCall { func: e, .. })
+        | Expr::Cast(ExprCast { expr: e, .. })
+        | Expr::Field(ExprField { base: e, .. })
+        | Expr::Index(ExprIndex { expr: e, .. })
+        | Expr::MethodCall(ExprMethodCall { receiver: e, .. })
+        | Expr::Range(ExprRange { from: Some(e), .. })
+        | Expr::Try(ExprTry { expr: e, .. })
+        | Expr::Type(ExprType { expr: e, .. }) => Some(e),
+        _ => None,
+    }
+}
+
+fn leading_operand_mut(expr: &mut Expr) -> Option<&mut Expr> {
+    match expr {
+        Expr::Assign(ExprAssign { left: e, .. })
+        | Expr::AssignOp(ExprAssignOp { left: e, .. })
+        | Expr::Await(ExprAwait { base: e, .. })
+        | Expr::Binary(ExprBinary { left: e, .. })
+        | Expr::Call(ExprCall { func: e, .. })
+        | Expr::Cast(ExprCast { expr: e, .. })
+        | Expr::Field(ExprField { base: e, .. })
+        | Expr::Index(ExprIndex { expr: e, .. })
+        | Expr::MethodCall(ExprMethodCall { receiver: e, .. })
+        | Expr::Range(ExprRange { from: Some(e), .. })
+        | Expr::Try(ExprTry { expr: e, .. })
+        | Expr::Type(ExprType { expr: e, .. }) => Some(e),
+        _ => None,
+    }
+}
@@ -1,4 +1,5 @@
 use crate::algorithm::Printer;
+use crate::color::{self, Category};
 use proc_macro2::{Delimiter, Ident, Literal, Spacing, TokenStream, TokenTree};
 
 impl Printer {
@@ -31,7 +32,13 @@ impl Printer {
     }
 
     pub fn ident(&mut self, ident: &Ident) {
-        self.word(ident.to_string());
+        let ident = ident.to_string();
+        let category = if color::is_keyword(&ident) {
+            Category::Keyword
+        } else {
+            Category::Ident
+        };
+        self.highlighted(category, ident);
     }
 
     pub fn token_punct(&mut self, ch: char) {
@@ -39,7 +46,7 @@ impl Printer {
     }
 
     pub fn token_literal(&mut self, literal: &Literal) {
-        self.word(literal.to_string());
+        self.highlighted(Category::Literal, literal.to_string());
     }
 
     pub fn delimiter_open(&mut self, delimiter: Delimiter) {
@@ -1,11 +1,13 @@
 use crate::algorithm::Printer;
+use crate::color::Category;
 use crate::iter::IterDelimited;
 use crate::INDENT;
 use proc_macro2::TokenStream;
+use syn::punctuated::Punctuated;
 use syn::{
-    Abi, BareFnArg, ReturnType, Type, TypeArray, TypeBareFn, TypeGroup, TypeImplTrait, TypeInfer,
-    TypeMacro, TypeNever, TypeParen, TypePath, TypePtr, TypeReference, TypeSlice, TypeTraitObject,
-    TypeTuple, Variadic,
+    Abi, BareFnArg, ReturnType, Token, Type, TypeArray, TypeBareFn, TypeGroup, TypeImplTrait,
+    TypeInfer, TypeMacro, TypeNever, TypeParamBound, TypeParen, TypePath, TypePtr, TypeReference,
+    TypeSlice, TypeTraitObject, TypeTuple, Variadic,
 };
 
 impl Printer {
@@ -26,9 +28,6 @@ impl Printer {
             Type::TraitObject(ty) => self.type_trait_object(ty),
             Type::Tuple(ty) => self.type_tuple(ty),
             Type::Verbatim(ty) => self.type_verbatim(ty),
-            #[cfg(test)]
-            Type::__TestExhaustive(_) => unreachable!(),
-            #[cfg(not(test))]
             _ => unimplemented!("unknown Type"),
         }
     }
@@ -74,12 +73,7 @@ impl Printer {
 
     fn type_impl_trait(&mut self, ty: &TypeImplTrait) {
         self.word("impl ");
-        for type_param_bound in ty.bounds.iter().delimited() {
-            if !type_param_bound.is_first {
-                self.word(" + ");
-            }
-            self.type_param_bound(&type_param_bound);
-        }
+        self.type_bounds(&ty.bounds);
     }
 
     fn type_infer(&mut self, ty: &TypeInfer) {
@@ -97,6 +91,9 @@ impl Printer {
     }
 
     fn type_paren(&mut self, ty: &TypeParen) {
+        if self.config.remove_nested_parens && !needs_parens(&ty.elem) {
+            return self.ty(&ty.elem);
+        }
         self.word("(");
         self.ty(&ty.elem);
         self.word(")");
@@ -136,12 +133,27 @@ impl Printer {
 
     fn type_trait_object(&mut self, ty: &TypeTraitObject) {
         self.word("dyn ");
-        for type_param_bound in ty.bounds.iter().delimited() {
-            if !type_param_bound.is_first {
-                self.word(" + ");
+        self.type_bounds(&ty.bounds);
+    }
+
+    // Breaks before a `+` when the bounds don't fit, indenting the following
+    // bounds. Like the left operand of a binary expression, the first bound
+    // is boxed so that breaks inside its generic arguments are indented
+    // relative to the start of the type rather than the continuation lines.
+    fn type_bounds(&mut self, bounds: &Punctuated<TypeParamBound, Token![+]>) {
+        self.ibox(INDENT);
+        for type_param_bound in bounds.iter().delimited() {
+            if type_param_bound.is_first {
+                self.ibox(-INDENT);
+                self.type_param_bound(&type_param_bound);
+                self.end();
+            } else {
+                self.space();
+                self.word("+ ");
+                self.type_param_bound(&type_param_bound);
             }
-            self.type_param_bound(&type_param_bound);
         }
+        self.end();
     }
 
     fn type_tuple(&mut self, ty: &TypeTuple) {
@@ -150,7 +162,12 @@ impl Printer {
         self.zerobreak();
         for elem in ty.elems.iter().delimited() {
             self.ty(&elem);
-            self.trailing_comma(elem.is_last);
+            if ty.elems.len() == 1 {
+                self.word(",");
+                self.zerobreak();
+            } else {
+                self.trailing_comma(elem.is_last);
+            }
         }
         self.offset(-INDENT);
         self.end();
@@ -158,7 +175,7 @@ impl Printer {
     }
 
     fn type_verbatim(&mut self, ty: &TokenStream) {
-        unimplemented!("Type::Verbatim `{}`", ty);
+        self.verbatim("Type", ty);
     }
 
     pub fn return_type(&mut self, ty: &ReturnType) {
@@ -175,7 +192,7 @@ impl Printer {
         self.outer_attrs(&bare_fn_arg.attrs);
         if let Some((name, _colon)) = &bare_fn_arg.name {
             self.ident(name);
-            self.word(": ");
+            self.type_annotation_colon();
         }
         self.ty(&bare_fn_arg.ty);
     }
@@ -190,6 +207,21 @@ impl Printer {
         if let Some(name) = &abi.name {
             self.lit_str(name);
             self.nbsp();
+        } else if self.config.force_explicit_abi {
+            self.highlighted(Category::Literal, "\"C\"");
+            self.nbsp();
         }
     }
 }
+
+// Types that need to keep their parens in every position a parenthesized type
+// can appear. A bound list like `dyn A + B` would otherwise be ambiguous in
+// `&(dyn A + B)` or as the return type in `fn() -> (dyn A + B)`.
+fn needs_parens(ty: &Type) -> bool {
+    match ty {
+        Type::Group(ty) => needs_parens(&ty.elem),
+        Type::ImplTrait(ty) => ty.bounds.len() > 1,
+        Type::TraitObject(ty) => ty.bounds.len() > 1,
+        _ => false,
+    }
+}